@@ -0,0 +1,263 @@
+use std::fmt::{Display, Formatter};
+
+use async_trait::async_trait;
+use log::trace;
+use serde_json::Value;
+
+use crate::auth::Authorized;
+use crate::comments::response::CommentResponse;
+use crate::comments::{Comment, CommentType};
+use crate::error::reddit_error::RedditError;
+use crate::error::Error;
+use crate::responses::api::Things;
+use crate::responses::{FullName, HasFullName, RedditResponse, RedditTypeResponse};
+use crate::submission::response::SubmissionResponse;
+use crate::submission::{Submission, SubmissionType};
+use crate::utils::request::ApiRequest;
+use crate::Client;
+
+/// The direction of a vote
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoteDirection {
+    /// Upvote
+    Up,
+    /// Removes the vote
+    Clear,
+    /// Downvote
+    Down,
+}
+
+impl Display for VoteDirection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            VoteDirection::Up => "1",
+            VoteDirection::Clear => "0",
+            VoteDirection::Down => "-1",
+        };
+        write!(f, "{}", string)
+    }
+}
+
+fn join(full_names: &[FullName]) -> String {
+    full_names
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl<A: Authorized> Client<A> {
+    /// Votes on a submission or comment
+    pub async fn vote(&self, full_name: FullName, direction: VoteDirection) -> Result<(), Error> {
+        trace!("Voting {} on {}", direction, full_name);
+        let request = ApiRequest::new("/api/vote")
+            .form("id", full_name)
+            .form("dir", direction);
+        self.post_empty(&request.url(), true, request.body()).await
+    }
+    /// Saves a submission or comment. Categories require Reddit Premium
+    pub async fn save(&self, full_name: FullName, category: Option<&str>) -> Result<(), Error> {
+        let request = ApiRequest::new("/api/save")
+            .form("id", full_name)
+            .form_opt("category", category);
+        self.post_empty(&request.url(), true, request.body()).await
+    }
+    /// Unsaves a submission or comment
+    pub async fn unsave(&self, full_name: FullName) -> Result<(), Error> {
+        let request = ApiRequest::new("/api/unsave").form("id", full_name);
+        self.post_empty(&request.url(), true, request.body()).await
+    }
+    /// Hides submissions from the user's listings
+    pub async fn hide(&self, full_names: &[FullName]) -> Result<(), Error> {
+        let request = ApiRequest::new("/api/hide").form("id", join(full_names));
+        self.post_empty(&request.url(), true, request.body()).await
+    }
+    /// Unhides submissions
+    pub async fn unhide(&self, full_names: &[FullName]) -> Result<(), Error> {
+        let request = ApiRequest::new("/api/unhide").form("id", join(full_names));
+        self.post_empty(&request.url(), true, request.body()).await
+    }
+    /// Reports a submission, comment or message to the moderators
+    pub async fn report(&self, full_name: FullName, reason: &str) -> Result<(), Error> {
+        let request = ApiRequest::new("/api/report")
+            .form("api_type", "json")
+            .form("thing_id", full_name)
+            .form("reason", reason);
+        self.post_api::<Value>(&request.url(), request.body())
+            .await?;
+        Ok(())
+    }
+    /// Replies to a submission, comment or message. Returns the new comment or message
+    pub async fn reply(&self, parent: FullName, text: &str) -> Result<RedditResponse, Error> {
+        trace!("Replying to {}", parent);
+        let request = ApiRequest::new("/api/comment")
+            .form("api_type", "json")
+            .form("thing_id", parent)
+            .form("text", text);
+        self.post_things(request).await
+    }
+    /// Edits the text of a comment or self post. Returns the updated thing
+    pub async fn edit(&self, full_name: FullName, text: &str) -> Result<RedditResponse, Error> {
+        trace!("Editing {}", full_name);
+        let request = ApiRequest::new("/api/editusertext")
+            .form("api_type", "json")
+            .form("thing_id", full_name)
+            .form("text", text);
+        self.post_things(request).await
+    }
+    /// Deletes a comment or submission made by the user
+    pub async fn delete(&self, full_name: FullName) -> Result<(), Error> {
+        trace!("Deleting {}", full_name);
+        let request = ApiRequest::new("/api/del").form("id", full_name);
+        self.post_empty(&request.url(), true, request.body()).await
+    }
+    async fn post_things(&self, request: ApiRequest) -> Result<RedditResponse, Error> {
+        self.post_api::<Things>(&request.url(), request.body())
+            .await?
+            .ok_or_else(|| Error::from("Reddit did not return the thing"))?
+            .into_first()
+    }
+}
+
+fn into_comment(response: RedditResponse) -> Result<CommentResponse, Error> {
+    match response.data {
+        RedditTypeResponse::Comment(comment) => Ok(comment),
+        _ => Err(RedditError::InvalidDataType(response.kind.to_string()).into()),
+    }
+}
+
+fn into_submission(response: RedditResponse) -> Result<SubmissionResponse, Error> {
+    match response.data {
+        RedditTypeResponse::Link(submission) => Ok(submission),
+        _ => Err(RedditError::InvalidDataType(response.kind.to_string()).into()),
+    }
+}
+
+/// Voting, saving, hiding, reporting and deleting for anything identified by a FullName
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() ->anyhow::Result<()>{
+///    use std::env;
+///    use rraw::actions::Actionable;
+///    use rraw::auth::PasswordAuthenticator;
+///    use rraw::submission::{SubmissionRetriever, SubmissionType};
+///    use rraw::Client;
+///    let client = Client:: login(PasswordAuthenticator::new(env::var("CLIENT_ID")?,env::var("CLIENT_SECRET")?,env::var("USERNAME")?,env::var("PASSWORD")?), "RRAW Test (by u/KingTuxWH)").await?;
+///    let subreddit = client.subreddit("rust").await?;
+///    let hot = subreddit.hot(None).await?;
+///    for submission in hot.data.children.iter() {
+///        submission.data.to_submission(&client).upvote().await?;
+///    }
+///    Ok(())
+/// }
+/// ```
+#[async_trait(?Send)]
+pub trait Actionable<A: Authorized> {
+    /// The Client used to make the requests
+    fn action_client(&self) -> &Client<A>;
+    /// The FullName of the target
+    fn action_target(&self) -> FullName;
+
+    /// Upvotes
+    async fn upvote(&self) -> Result<(), Error> {
+        self.action_client()
+            .vote(self.action_target(), VoteDirection::Up)
+            .await
+    }
+    /// Downvotes
+    async fn downvote(&self) -> Result<(), Error> {
+        self.action_client()
+            .vote(self.action_target(), VoteDirection::Down)
+            .await
+    }
+    /// Removes the vote
+    async fn clear_vote(&self) -> Result<(), Error> {
+        self.action_client()
+            .vote(self.action_target(), VoteDirection::Clear)
+            .await
+    }
+    /// Saves. Categories require Reddit Premium
+    async fn save(&self, category: Option<&str>) -> Result<(), Error> {
+        self.action_client()
+            .save(self.action_target(), category)
+            .await
+    }
+    /// Unsaves
+    async fn unsave(&self) -> Result<(), Error> {
+        self.action_client().unsave(self.action_target()).await
+    }
+    /// Hides from the user's listings
+    async fn hide(&self) -> Result<(), Error> {
+        self.action_client().hide(&[self.action_target()]).await
+    }
+    /// Unhides
+    async fn unhide(&self) -> Result<(), Error> {
+        self.action_client().unhide(&[self.action_target()]).await
+    }
+    /// Reports to the moderators
+    async fn report(&self, reason: &str) -> Result<(), Error> {
+        self.action_client()
+            .report(self.action_target(), reason)
+            .await
+    }
+    /// Deletes. Only works on the user's own submissions and comments
+    async fn delete(&self) -> Result<(), Error> {
+        self.action_client().delete(self.action_target()).await
+    }
+}
+
+impl<'a, A, T> Actionable<A> for Submission<'a, A, T>
+where
+    A: Authorized,
+    T: SubmissionType<'a> + HasFullName,
+{
+    fn action_client(&self) -> &Client<A> {
+        self.me
+    }
+    fn action_target(&self) -> FullName {
+        self.submission.full_name()
+    }
+}
+
+impl<'a, A, T> Submission<'a, A, T>
+where
+    A: Authorized,
+    T: SubmissionType<'a> + HasFullName,
+{
+    /// Replies with a top level comment
+    pub async fn reply(&self, text: &str) -> Result<CommentResponse, Error> {
+        into_comment(self.me.reply(self.action_target(), text).await?)
+    }
+    /// Edits the text of a self post
+    pub async fn edit(&self, text: &str) -> Result<SubmissionResponse, Error> {
+        into_submission(self.me.edit(self.action_target(), text).await?)
+    }
+}
+
+impl<'a, A, T> Comment<'a, A, T>
+where
+    A: Authorized,
+    T: CommentType<'a> + HasFullName,
+{
+    /// Replies to the comment
+    pub async fn reply(&self, text: &str) -> Result<CommentResponse, Error> {
+        into_comment(self.me.reply(self.action_target(), text).await?)
+    }
+    /// Edits the text of the comment
+    pub async fn edit(&self, text: &str) -> Result<CommentResponse, Error> {
+        into_comment(self.me.edit(self.action_target(), text).await?)
+    }
+}
+
+impl<'a, A, T> Actionable<A> for Comment<'a, A, T>
+where
+    A: Authorized,
+    T: CommentType<'a> + HasFullName,
+{
+    fn action_client(&self) -> &Client<A> {
+        self.me
+    }
+    fn action_target(&self) -> FullName {
+        self.comment.full_name()
+    }
+}
@@ -0,0 +1,219 @@
+use std::fmt::{Debug, Formatter};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::auth::{expires_within, Scope, TokenResponseData, AUTH_CONTENT_TYPE};
+use crate::{utils, Authenticator};
+use async_trait::async_trait;
+use log::warn;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use reqwest::{Body, Client, Method};
+
+use crate::builder::Endpoints;
+use crate::error::http_error::check_status;
+use crate::error::internal_error::InternalError;
+use crate::error::Error;
+use crate::utils::request::Params;
+
+/// The grant type of installed apps
+pub static INSTALLED_CLIENT_GRANT: &str = "https://oauth.reddit.com/grants/installed_client";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Grant {
+    ClientCredentials,
+    InstalledClient { device_id: String },
+}
+
+/// Application only OAuth. No user is logged in, but requests get the OAuth rate limits
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() ->anyhow::Result<()>{
+///    use std::env;
+///    use rraw::auth::ClientCredentialsAuthenticator;
+///    use rraw::Client;
+///    let client = Client::login(ClientCredentialsAuthenticator::new(env::var("CLIENT_ID")?, env::var("CLIENT_SECRET")?), "RRAW Test (by u/KingTuxWH)").await?;
+///    let subreddit = client.subreddit("rust").await?;
+///    Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ClientCredentialsAuthenticator {
+    /// Token
+    pub token: Option<String>,
+    /// When does it expire
+    pub expiration_time: Option<u128>,
+    /// The granted scopes
+    scopes: Option<Vec<Scope>>,
+    /// Client ID
+    client_id: String,
+    /// Client Secret
+    client_secret: String,
+    grant: Grant,
+}
+
+impl Debug for ClientCredentialsAuthenticator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[ClientCredentialsAuthenticator] Token Defined: {} Expires At {}",
+            self.token.is_some(),
+            self.expiration_time.unwrap_or(0)
+        )
+    }
+}
+
+impl ClientCredentialsAuthenticator {
+    /// Creates a new Authenticator using the `client_credentials` grant. For confidential clients (Web and Script APPs)
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<S: Into<String>>(client_id: S, client_secret: S) -> ClientCredentialsAuthenticator {
+        ClientCredentialsAuthenticator {
+            token: None,
+            expiration_time: None,
+            scopes: None,
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            grant: Grant::ClientCredentials,
+        }
+    }
+    /// Creates a new Authenticator using the installed client grant. For Installed APPs
+    ///
+    /// The device_id must be unique per device and 20-30 characters long. `DO_NOT_TRACK_THIS_DEVICE` is also accepted
+    pub fn installed<S: Into<String>>(
+        client_id: S,
+        device_id: S,
+    ) -> ClientCredentialsAuthenticator {
+        ClientCredentialsAuthenticator {
+            token: None,
+            expiration_time: None,
+            scopes: None,
+            client_id: client_id.into(),
+            client_secret: String::new(),
+            grant: Grant::InstalledClient {
+                device_id: device_id.into(),
+            },
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Authenticator for ClientCredentialsAuthenticator {
+    /// Logs in
+    async fn login(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        let url = endpoints.access_token_url();
+        let body = match &self.grant {
+            Grant::ClientCredentials => Params::new().with("grant_type", "client_credentials"),
+            Grant::InstalledClient { device_id } => Params::new()
+                .with("grant_type", INSTALLED_CLIENT_GRANT)
+                .with("device_id", device_id),
+        };
+        let mut header = HeaderMap::new();
+        header.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!(
+                "Basic {}",
+                utils::basic_header(&self.client_id, &self.client_secret)
+            ))
+            .unwrap(),
+        );
+        header.insert(USER_AGENT, HeaderValue::from_str(user_agent).unwrap());
+        header.insert(CONTENT_TYPE, AUTH_CONTENT_TYPE.clone());
+        let response = client
+            .post(url)
+            .body(Body::from(body))
+            .headers(header)
+            .send()
+            .await
+            .map_err(InternalError::from)?;
+        let response = check_status(Method::POST, response).await?;
+
+        let token: TokenResponseData = response.json().await?;
+        self.scopes = Some(Scope::parse_granted(&token.scope));
+        self.token = Some(token.access_token);
+        let x = token.expires_in * 1000;
+        let x1 = (x as u128)
+            + SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis();
+        self.expiration_time = Some(x1);
+        return Ok(true);
+    }
+    /// Logs out
+    async fn logout(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<(), Error> {
+        let token = match self.token.as_ref() {
+            Some(token) => token,
+            // No token to revoke
+            None => return Ok(()),
+        };
+        let url = endpoints.revoke_token_url();
+        let body = Params::new()
+            .with("token", token)
+            .with("token_type_hint", "access_token");
+
+        let mut header = HeaderMap::new();
+        header.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!(
+                "Basic {}",
+                utils::basic_header(&self.client_id, &self.client_secret)
+            ))
+            .unwrap(),
+        );
+        header.insert(USER_AGENT, HeaderValue::from_str(user_agent).unwrap());
+        header.insert(CONTENT_TYPE, AUTH_CONTENT_TYPE.clone());
+        let response = client
+            .post(url)
+            .body(Body::from(body))
+            .headers(header)
+            .send()
+            .await?;
+        check_status(Method::POST, response).await?;
+        self.token = None;
+        self.expiration_time = None;
+        Ok(())
+    }
+    /// Requests a new token. Application only tokens have no refresh token
+    async fn token_refresh(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        self.login(client, user_agent, endpoints).await
+    }
+    /// headers
+    fn headers(&self, headers: &mut HeaderMap) {
+        if let Some(token) = self.token.as_ref() {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+            );
+        } else {
+            warn!("No token found");
+        }
+    }
+    /// True
+    fn oauth(&self) -> bool {
+        true
+    }
+    /// True if the token expires within the margin
+    fn needs_token_refresh(&self, margin: Duration) -> bool {
+        expires_within(self.expiration_time, margin)
+    }
+    /// Always None
+    fn get_refresh_token(&self) -> Option<String> {
+        Option::None
+    }
+    fn scopes(&self) -> Option<Vec<Scope>> {
+        self.scopes.clone()
+    }
+}
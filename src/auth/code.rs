@@ -1,16 +1,22 @@
-use std::fmt::{Debug, Formatter};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::auth::{TokenResponseData, AUTH_CONTENT_TYPE};
-use crate::{Authenticator, Authorized, utils};
+use crate::auth::{
+    expires_within, Scope, StoredToken, TokenResponseData, TokenStore, AUTH_CONTENT_TYPE,
+    DEFAULT_REFRESH_MARGIN,
+};
+use crate::{utils, Authenticator, Authorized};
 use async_trait::async_trait;
 use log::warn;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
-use reqwest::{Body, Client};
+use reqwest::{Body, Client, Method};
 
-use crate::error::http_error::IntoResult;
+use crate::builder::Endpoints;
+use crate::error::http_error::check_status;
 use crate::error::internal_error::InternalError;
 use crate::error::Error;
+use crate::utils::request::Params;
 
 #[derive(Clone)]
 pub struct CodeAuthenticator {
@@ -20,6 +26,8 @@ pub struct CodeAuthenticator {
     pub expiration_time: Option<u128>,
     /// Refresh token
     pub refresh_token: Option<String>,
+    /// The granted scopes
+    pub(crate) scopes: Option<Vec<Scope>>,
     /// Client ID
     pub(crate) client_id: String,
     /// Client Secret
@@ -28,6 +36,10 @@ pub struct CodeAuthenticator {
     authorization_code: String,
     /// Redirect URI
     redirect_uri: String,
+    /// Where the tokens are kept between restarts
+    pub(crate) store: Option<Arc<dyn TokenStore>>,
+    /// Stored tokens that expire within it are not used
+    pub(crate) refresh_margin: Duration,
 }
 
 impl Debug for CodeAuthenticator {
@@ -56,30 +68,143 @@ impl CodeAuthenticator {
         CodeAuthenticator {
             token: None,
             expiration_time: None,
+            scopes: None,
             refresh_token: None,
             client_id: client_id.into(),
             client_secret: client_secret.into(),
             authorization_code: authorization_code.into(),
             redirect_uri: redirect_uri.into(),
+            store: None,
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
         }
     }
-    /// This method does not check the values of the parameters.
+    /// Loads the tokens from the store before logging in and saves them after Reddit issues new ones.
+    ///
+    /// If the store has a refresh token the authorization code is not used
+    pub fn with_store<S: TokenStore + 'static>(mut self, store: S) -> CodeAuthenticator {
+        self.store = Some(Arc::new(store));
+        self
+    }
+    /// Takes the tokens of the store. Returns true if the stored access token differs from the current one
+    /// and is valid for longer than the refresh margin
+    async fn restore(&mut self) -> Result<bool, Error> {
+        let stored = match &self.store {
+            Some(store) => store.load().await?,
+            None => None,
+        };
+        let stored = match stored {
+            Some(stored) => stored,
+            None => return Ok(false),
+        };
+        if stored.refresh_token.is_some() {
+            self.refresh_token = stored.refresh_token.clone();
+        }
+        if stored.token == self.token || stored.expires_within(self.refresh_margin) {
+            return Ok(false);
+        }
+        self.token = stored.token;
+        self.expiration_time = stored.expiration_time;
+        self.scopes = stored.scope.as_deref().map(Scope::parse_granted);
+        Ok(true)
+    }
+    async fn persist(&self) -> Result<(), Error> {
+        if let Some(store) = &self.store {
+            let token = StoredToken {
+                token: self.token.clone(),
+                expiration_time: self.expiration_time,
+                scope: self.scopes.as_ref().map(|scopes| {
+                    scopes
+                        .iter()
+                        .map(Scope::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                }),
+                refresh_token: self.refresh_token.clone(),
+            };
+            store.save(&token).await?;
+        }
+        Ok(())
+    }
+    /// Trades the refresh token for a new access token
+    async fn refresh_grant(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        let refresh_token = self.refresh_token.clone().ok_or(Error::NoRefreshToken)?;
+        let url = endpoints.access_token_url();
+        let body = Params::new()
+            .with("grant_type", "refresh_token")
+            .with("refresh_token", refresh_token);
+        let mut header = HeaderMap::new();
+        header.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!(
+                "Basic {}",
+                utils::basic_header(&self.client_id, &self.client_secret)
+            ))
+            .unwrap(),
+        );
+        header.insert(USER_AGENT, HeaderValue::from_str(user_agent).unwrap());
+        header.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_str("application/x-www-form-urlencoded").unwrap(),
+        );
+        let response = client
+            .post(url)
+            .body(Body::from(body))
+            .headers(header)
+            .send()
+            .await
+            .map_err(InternalError::from)?;
+        let response = check_status(Method::POST, response).await?;
+
+        let token: TokenResponseData = response.json().await?;
+        self.scopes = Some(Scope::parse_granted(&token.scope));
+
+        self.token = Some(token.access_token);
+        let x = token.expires_in * 1000;
+        let x1 = (x as u128)
+            + SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis();
+        self.expiration_time = Some(x1);
+        self.persist().await?;
+        Ok(true)
+    }
+    /// This method does not check the values of the parameters. The scopes can be a [Scope] or a `&str`.
+    /// The URL points at [Endpoints::authorize_url]
     ///
     /// Information of the data can be found [here](https://github.com/reddit-archive/reddit/wiki/OAuth2).
     pub fn generate_authorization_url(
+        endpoints: &Endpoints,
         client_id: impl AsRef<str>,
         redirect_uri: impl AsRef<str>,
         state: impl AsRef<str>,
         duration: impl AsRef<str>,
-        scope: Vec<&str>,
+        scope: Vec<impl Display>,
     ) -> String {
+        let query = Params::new()
+            .with("client_id", client_id.as_ref())
+            .with("response_type", "code")
+            .with("state", state.as_ref())
+            .with("redirect_uri", redirect_uri.as_ref())
+            .with("duration", duration.as_ref());
+        // Reddit documents the scopes as comma separated so the commas are kept as is
+        let scope = scope
+            .iter()
+            .map(|scope| {
+                form_urlencoded::byte_serialize(scope.to_string().as_bytes()).collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(",");
         format!(
-            "https://www.reddit.com/api/v1/authorize?client_id={}&response_type=code&state={}&redirect_uri={}&duration={}&scope={}",
-            client_id.as_ref(),
-            state.as_ref(),
-            redirect_uri.as_ref(),
-            duration.as_ref(),
-            scope.join(",")
+            "{}?{}&scope={}",
+            endpoints.authorize_url(),
+            query.encode(),
+            scope
         )
     }
 }
@@ -87,17 +212,27 @@ impl CodeAuthenticator {
 #[async_trait(?Send)]
 impl Authenticator for CodeAuthenticator {
     /// Logs in
-    async fn login(&mut self, client: &Client, user_agent: &str) -> Result<bool, Error> {
-        let url = "https://www.reddit.com/api/v1/access_token";
-        let body = format!(
-            "grant_type=authorization_code&code={}&redirect_uri={}",
-            &self.authorization_code.trim_end_matches("#_"),
-            &self.redirect_uri
-        );
+    async fn login(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        if self.token.is_none() && self.restore().await? {
+            return Ok(true);
+        }
+        if self.refresh_token.is_some() {
+            return self.refresh_grant(client, user_agent, endpoints).await;
+        }
+        let url = endpoints.access_token_url();
+        let body = Params::new()
+            .with("grant_type", "authorization_code")
+            .with("code", self.authorization_code.trim_end_matches("#_"))
+            .with("redirect_uri", &self.redirect_uri);
         let mut header = HeaderMap::new();
         header.insert(
             AUTHORIZATION,
-            HeaderValue::from_str(&*format!(
+            HeaderValue::from_str(&format!(
                 "Basic {}",
                 utils::basic_header(&self.client_id, &self.client_secret)
             ))
@@ -115,9 +250,10 @@ impl Authenticator for CodeAuthenticator {
             .send()
             .await
             .map_err(InternalError::from)?;
-        response.status().into_result()?;
+        let response = check_status(Method::POST, response).await?;
 
         let token: TokenResponseData = response.json().await?;
+        self.scopes = Some(Scope::parse_granted(&token.scope));
 
         self.token = Some(token.access_token);
         let x = token.expires_in * 1000;
@@ -130,15 +266,25 @@ impl Authenticator for CodeAuthenticator {
         if !token.refresh_token.is_empty() {
             self.refresh_token = Some(token.refresh_token);
         }
+        self.persist().await?;
         return Ok(true);
     }
     /// Logs out
-    async fn logout(&mut self, client: &Client, user_agent: &str) -> Result<(), Error> {
-        let url = "https://www.reddit.com/api/v1/revoke_token";
+    async fn logout(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<(), Error> {
+        let url = endpoints.revoke_token_url();
         let body = if let Some(refresh_token) = &self.refresh_token {
-            format!("token={}&token_type_hint=refresh_token", refresh_token)
+            Params::new()
+                .with("token", refresh_token)
+                .with("token_type_hint", "refresh_token")
         } else if let Some(token) = self.token.as_ref() {
-            format!("token={}&token_type_hint=access_token", token)
+            Params::new()
+                .with("token", token)
+                .with("token_type_hint", "access_token")
         } else {
             // No token to revoke
             return Ok(());
@@ -153,53 +299,27 @@ impl Authenticator for CodeAuthenticator {
             .headers(header)
             .send()
             .await?;
-        response.status().into_result()?;
+        check_status(Method::POST, response).await?;
         self.token = None;
         self.expiration_time = None;
         self.refresh_token = None;
+        if let Some(store) = &self.store {
+            store.clear().await?;
+        }
         Ok(())
     }
     /// Returns true if successful
-    async fn token_refresh(&mut self, client: &Client, user_agent: &str) -> Result<bool, Error> {
-        let url = "https://www.reddit.com/api/v1/access_token";
-        let body = format!(
-            "grant_type=refresh_token&refresh_token={}",
-            &self.refresh_token.to_owned().unwrap()
-        );
-        let mut header = HeaderMap::new();
-        header.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&*format!(
-                "Basic {}",
-                utils::basic_header(&self.client_id, &self.client_secret)
-            ))
-            .unwrap(),
-        );
-        header.insert(USER_AGENT, HeaderValue::from_str(user_agent).unwrap());
-        header.insert(
-            CONTENT_TYPE,
-            HeaderValue::from_str("application/x-www-form-urlencoded").unwrap(),
-        );
-        let response = client
-            .post(url)
-            .body(Body::from(body))
-            .headers(header)
-            .send()
-            .await
-            .map_err(InternalError::from)?;
-        response.status().into_result()?;
-
-        let token: TokenResponseData = response.json().await?;
-
-        self.token = Some(token.access_token);
-        let x = token.expires_in * 1000;
-        let x1 = (x as u128)
-            + SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_millis();
-        self.expiration_time = Some(x1);
-        return Ok(true);
+    async fn token_refresh(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        // Another worker sharing the store might have refreshed it already
+        if self.restore().await? {
+            return Ok(true);
+        }
+        self.refresh_grant(client, user_agent, endpoints).await
     }
     // headers
     fn headers(&self, headers: &mut HeaderMap) {
@@ -216,25 +336,23 @@ impl Authenticator for CodeAuthenticator {
     fn oauth(&self) -> bool {
         true
     }
-    /// Validates Time
-    fn needs_token_refresh(&self) -> bool {
-        let i = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
+    /// True if the token expires within the margin. Without a refresh token only once it expired
+    fn needs_token_refresh(&self, margin: Duration) -> bool {
         if self.refresh_token.is_some() {
-            if self.expiration_time.is_none() {
-                true
-            } else {
-                i >= self.expiration_time.unwrap()
-            }
+            expires_within(self.expiration_time, margin)
         } else {
-            false
+            expires_within(self.expiration_time, Duration::ZERO)
         }
     }
     fn get_refresh_token(&self) -> Option<String> {
         self.refresh_token.to_owned()
     }
+    fn scopes(&self) -> Option<Vec<Scope>> {
+        self.scopes.clone()
+    }
+    fn set_refresh_margin(&mut self, margin: Duration) {
+        self.refresh_margin = margin;
+    }
 }
 
 impl Authorized for CodeAuthenticator {}
@@ -0,0 +1,260 @@
+//! Runs the authorization code flow against a redirect listener on localhost
+use std::fmt::{Debug, Display, Formatter};
+use std::net::SocketAddr;
+
+use log::{debug, trace};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::auth::CodeAuthenticator;
+use crate::builder::Endpoints;
+use crate::error::internal_error::InternalError;
+use crate::error::Error;
+
+/// Requests larger than this are not a redirect from Reddit
+const MAX_REQUEST_LENGTH: usize = 8 * 1024;
+
+/// Waits for Reddit to redirect the browser back with the authorization code.
+///
+/// The redirect URI must be a `http://` URI on `localhost` or a loopback address and match the one registered for the app.
+/// Using port `0` binds a free port and updates the redirect URI
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() ->anyhow::Result<()>{
+///    use std::env;
+///    use rraw::auth::{LocalCodeFlow, Scope};
+///    use rraw::Client;
+///    let flow = LocalCodeFlow::bind(env::var("CLIENT_ID")?, env::var("CLIENT_SECRET")?, "http://localhost:8080/callback".to_string())
+///        .await?
+///        .duration("permanent")
+///        .scope(vec![Scope::Identity, Scope::Read]);
+///    println!("Open {}", flow.authorization_url());
+///    let auth = flow.wait().await?;
+///    let client = Client::login(auth, "RRAW Test (by u/KingTuxWH)").await?;
+///    Ok(())
+/// }
+/// ```
+pub struct LocalCodeFlow {
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    path: String,
+    state: String,
+    duration: String,
+    scope: Vec<String>,
+    endpoints: Endpoints,
+    listener: TcpListener,
+}
+
+impl Debug for LocalCodeFlow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[LocalCodeFlow] Redirect URI: {}", self.redirect_uri)
+    }
+}
+
+impl LocalCodeFlow {
+    /// Binds the listener to the host and port of the redirect URI and generates a random state
+    pub async fn bind<S: Into<String>>(
+        client_id: S,
+        client_secret: S,
+        redirect_uri: S,
+    ) -> Result<LocalCodeFlow, Error> {
+        let redirect_uri = redirect_uri.into();
+        let (host, path) = split_redirect_uri(&redirect_uri)?;
+        let listener = TcpListener::bind(host)
+            .await
+            .map_err(InternalError::IOError)?;
+        let address = listener.local_addr().map_err(InternalError::IOError)?;
+        let redirect_uri = if host.ends_with(":0") {
+            format!(
+                "http://{}:{}{}",
+                host.trim_end_matches(":0"),
+                address.port(),
+                path
+            )
+        } else {
+            redirect_uri.clone()
+        };
+        debug!("Listening for the authorization code on {}", address);
+        Ok(LocalCodeFlow {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            path: path.to_string(),
+            redirect_uri,
+            state: random_state()?,
+            duration: "temporary".to_string(),
+            scope: vec!["identity".to_string()],
+            endpoints: Endpoints::default(),
+            listener,
+        })
+    }
+    /// `temporary` or `permanent`. Only `permanent` issues a refresh token. Default `temporary`
+    pub fn duration<S: Into<String>>(mut self, duration: S) -> LocalCodeFlow {
+        self.duration = duration.into();
+        self
+    }
+    /// The scopes to request. Default `identity`
+    pub fn scope(mut self, scope: Vec<impl Display>) -> LocalCodeFlow {
+        self.scope = scope.iter().map(ToString::to_string).collect();
+        self
+    }
+    /// Overrides the Reddit endpoints the authorization URL points at
+    pub fn endpoints(mut self, endpoints: Endpoints) -> LocalCodeFlow {
+        self.endpoints = endpoints;
+        self
+    }
+    /// The random state sent to Reddit
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+    /// The redirect URI sent to Reddit
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+    /// The address the listener is bound to
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.listener.local_addr().map_err(InternalError::IOError)?)
+    }
+    /// The URL the user has to open in their browser
+    pub fn authorization_url(&self) -> String {
+        CodeAuthenticator::generate_authorization_url(
+            &self.endpoints,
+            &self.client_id,
+            &self.redirect_uri,
+            &self.state,
+            &self.duration,
+            self.scope.clone(),
+        )
+    }
+    /// Waits for the redirect. Requests to other paths are answered with a 404 and ignored
+    pub async fn wait(self) -> Result<CodeAuthenticator, Error> {
+        loop {
+            let (mut stream, address) = self
+                .listener
+                .accept()
+                .await
+                .map_err(InternalError::IOError)?;
+            trace!("Redirect listener accepted {}", address);
+            let target = match read_target(&mut stream).await? {
+                Some(target) => target,
+                None => {
+                    respond(&mut stream, "400 Bad Request", "Bad Request").await?;
+                    continue;
+                }
+            };
+            let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+            if path != self.path {
+                respond(&mut stream, "404 Not Found", "Not Found").await?;
+                continue;
+            }
+            let mut code = None;
+            let mut state = None;
+            let mut error = None;
+            for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+                match key.as_ref() {
+                    "code" => code = Some(value.into_owned()),
+                    "state" => state = Some(value.into_owned()),
+                    "error" => error = Some(value.into_owned()),
+                    _ => {}
+                }
+            }
+            if state.as_deref() != Some(self.state.as_str()) {
+                respond(&mut stream, "400 Bad Request", "The state did not match").await?;
+                return Err(Error::StateMismatch);
+            }
+            if let Some(error) = error {
+                respond(
+                    &mut stream,
+                    "200 OK",
+                    "Authorization failed. You can close this tab",
+                )
+                .await?;
+                return Err(Error::AuthorizationFailed(error));
+            }
+            let code = match code {
+                Some(code) => code,
+                None => {
+                    respond(&mut stream, "400 Bad Request", "No code was provided").await?;
+                    return Err(Error::AuthorizationFailed("missing_code".to_string()));
+                }
+            };
+            respond(&mut stream, "200 OK", "Authorized. You can close this tab").await?;
+            return Ok(CodeAuthenticator::new(
+                self.client_id,
+                self.client_secret,
+                code,
+                self.redirect_uri,
+            ));
+        }
+    }
+}
+
+/// Splits `http://localhost:8080/callback` into `localhost:8080` and `/callback`
+fn split_redirect_uri(redirect_uri: &str) -> Result<(&str, &str), Error> {
+    let rest = redirect_uri
+        .strip_prefix("http://")
+        .ok_or_else(|| Error::Other(format!("Redirect URI {} must use http://", redirect_uri)))?;
+    let (host, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let name = host.rsplit_once(':').map(|(name, _)| name).unwrap_or(host);
+    if !matches!(name, "localhost" | "127.0.0.1" | "[::1]") {
+        return Err(Error::Other(format!(
+            "Redirect URI {} must point to localhost",
+            redirect_uri
+        )));
+    }
+    if !host.contains(':') || host.ends_with(']') {
+        return Err(Error::Other(format!(
+            "Redirect URI {} must have a port",
+            redirect_uri
+        )));
+    }
+    Ok((host, path))
+}
+
+/// The request target of the first line. `GET /callback?code=... HTTP/1.1`
+async fn read_target(stream: &mut TcpStream) -> Result<Option<String>, Error> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 1024];
+    while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(InternalError::IOError)?;
+        if read == 0 || buffer.len() + read > MAX_REQUEST_LENGTH {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let request = String::from_utf8_lossy(&buffer);
+    let mut parts = request.lines().next().unwrap_or_default().split(' ');
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => Ok(Some(target.to_string())),
+        _ => Ok(None),
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, message: &str) -> Result<(), Error> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        message.len(),
+        message
+    );
+    stream
+        .write_all(response.as_bytes())
+        .await
+        .map_err(InternalError::IOError)?;
+    stream.shutdown().await.map_err(InternalError::IOError)?;
+    Ok(())
+}
+
+/// 128 bits from the operating system random number generator as hex
+fn random_state() -> Result<String, Error> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|error| InternalError::Custom(format!("Unable to generate a state {}", error)))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
@@ -1,18 +1,32 @@
 use std::fmt::{Debug, Formatter};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use reqwest::header::{HeaderMap, HeaderValue};
 use reqwest::Client;
 use serde::Deserialize;
 
+use crate::builder::Endpoints;
 use crate::error::Error;
 
+mod client_credentials;
 mod code;
+#[cfg(feature = "code_flow")]
+mod code_flow;
 mod password;
+mod scope;
+mod store;
 mod token;
 
+pub use client_credentials::{ClientCredentialsAuthenticator, INSTALLED_CLIENT_GRANT};
 pub use code::CodeAuthenticator;
+#[cfg(feature = "code_flow")]
+pub use code_flow::LocalCodeFlow;
 pub use password::PasswordAuthenticator;
+pub use scope::Scope;
+#[cfg(feature = "tokio")]
+pub use store::FileTokenStore;
+pub use store::{MemoryTokenStore, StoredToken, TokenStore};
 pub use token::TokenAuthenticator;
 
 pub static AUTH_CONTENT_TYPE: HeaderValue =
@@ -36,23 +50,59 @@ fn default_response() -> String {
 pub trait Authenticator: Clone + Send + Sync + Debug {
     /// Logins to the Reddit API
     /// true if successful
-    async fn login(&mut self, client: &Client, user_agent: &str) -> Result<bool, Error>;
+    async fn login(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error>;
     /// Releases the token back to Reddit
-    async fn logout(&mut self, client: &Client, user_agent: &str) -> Result<(), Error>;
+    async fn logout(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<(), Error>;
     /// true if successful
-    async fn token_refresh(&mut self, client: &Client, user_agent: &str) -> Result<bool, Error>;
+    async fn token_refresh(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error>;
     /// Header Values required for auth
     fn headers(&self, headers: &mut HeaderMap);
     /// Supports OAuth
     fn oauth(&self) -> bool;
-    /// Does the Token need refresh
-    fn needs_token_refresh(&self) -> bool;
+    /// Does the Token need refresh. True if it expires within the margin
+    fn needs_token_refresh(&self, margin: Duration) -> bool;
     /// Returns refresh token
     fn get_refresh_token(&self) -> Option<String>;
+    /// The scopes Reddit granted the token. None if unknown
+    fn scopes(&self) -> Option<Vec<Scope>> {
+        None
+    }
+    /// Called by the [ClientBuilder](crate::builder::ClientBuilder) with its refresh margin before logging in
+    fn set_refresh_margin(&mut self, _margin: Duration) {}
 }
 
 pub trait Authorized: Authenticator {}
 
+/// How long before the token expires it is refreshed. Unless changed with [ClientBuilder::refresh_margin](crate::builder::ClientBuilder::refresh_margin)
+pub(crate) const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// True if there is no expiration time or it is within the margin. The expiration time is in milliseconds since the Unix Epoch
+pub(crate) fn expires_within(expiration_time: Option<u128>, margin: Duration) -> bool {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    match expiration_time {
+        None => true,
+        Some(expiration_time) => now + margin.as_millis() >= expiration_time,
+    }
+}
+
 /// AnonymousAuthenticator
 #[derive(Clone, Default)]
 pub struct AnonymousAuthenticator;
@@ -66,15 +116,30 @@ impl Debug for AnonymousAuthenticator {
 #[async_trait(?Send)]
 impl Authenticator for AnonymousAuthenticator {
     /// Returns true because it is anonymous
-    async fn login(&mut self, _client: &Client, _user_agent: &str) -> Result<bool, Error> {
+    async fn login(
+        &mut self,
+        _client: &Client,
+        _user_agent: &str,
+        _endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
         Ok(true)
     }
     /// Does nothing
-    async fn logout(&mut self, _client: &Client, _user_agent: &str) -> Result<(), Error> {
+    async fn logout(
+        &mut self,
+        _client: &Client,
+        _user_agent: &str,
+        _endpoints: &Endpoints,
+    ) -> Result<(), Error> {
         Ok(())
     }
     /// Returns true because it is anonymous
-    async fn token_refresh(&mut self, _client: &Client, _user_agent: &str) -> Result<bool, Error> {
+    async fn token_refresh(
+        &mut self,
+        _client: &Client,
+        _user_agent: &str,
+        _endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
         Ok(true)
     }
     /// Does Nothing
@@ -84,7 +149,7 @@ impl Authenticator for AnonymousAuthenticator {
         false
     }
     /// Always false
-    fn needs_token_refresh(&self) -> bool {
+    fn needs_token_refresh(&self, _margin: Duration) -> bool {
         false
     }
     /// Always None
@@ -1,16 +1,18 @@
 use std::fmt::{Debug, Formatter};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::auth::{TokenResponseData, AUTH_CONTENT_TYPE};
-use crate::{Authenticator, Authorized, utils};
+use crate::auth::{expires_within, Scope, TokenResponseData, AUTH_CONTENT_TYPE};
+use crate::{utils, Authenticator, Authorized};
 use async_trait::async_trait;
 use log::warn;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
-use reqwest::{Body, Client};
+use reqwest::{Body, Client, Method};
 
-use crate::error::http_error::IntoResult;
+use crate::builder::Endpoints;
+use crate::error::http_error::check_status;
 use crate::error::internal_error::InternalError;
 use crate::error::Error;
+use crate::utils::request::Params;
 
 #[derive(Clone)]
 pub struct PasswordAuthenticator {
@@ -18,6 +20,8 @@ pub struct PasswordAuthenticator {
     pub token: Option<String>,
     /// When does it expire
     pub expiration_time: Option<u128>,
+    /// The granted scopes
+    scopes: Option<Vec<Scope>>,
     /// Client ID
     client_id: String,
     /// Client Secret
@@ -51,6 +55,7 @@ impl PasswordAuthenticator {
         PasswordAuthenticator {
             token: None,
             expiration_time: None,
+            scopes: None,
             client_id: client_id.into(),
             client_secret: client_secret.into(),
             username: username.into(),
@@ -62,16 +67,21 @@ impl PasswordAuthenticator {
 #[async_trait(?Send)]
 impl Authenticator for PasswordAuthenticator {
     /// Logs in
-    async fn login(&mut self, client: &Client, user_agent: &str) -> Result<bool, Error> {
-        let url = "https://www.reddit.com/api/v1/access_token";
-        let body = format!(
-            "grant_type=password&username={}&password={}",
-            &self.username, &self.password
-        );
+    async fn login(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        let url = endpoints.access_token_url();
+        let body = Params::new()
+            .with("grant_type", "password")
+            .with("username", &self.username)
+            .with("password", &self.password);
         let mut header = HeaderMap::new();
         header.insert(
             AUTHORIZATION,
-            HeaderValue::from_str(&*format!(
+            HeaderValue::from_str(&format!(
                 "Basic {}",
                 utils::basic_header(&self.client_id, &self.client_secret)
             ))
@@ -86,9 +96,10 @@ impl Authenticator for PasswordAuthenticator {
             .send()
             .await
             .map_err(InternalError::from)?;
-        response.status().into_result()?;
+        let response = check_status(Method::POST, response).await?;
 
         let token: TokenResponseData = response.json().await?;
+        self.scopes = Some(Scope::parse_granted(&token.scope));
         self.token = Some(token.access_token);
         let x = token.expires_in * 1000;
         let x1 = (x as u128)
@@ -100,9 +111,19 @@ impl Authenticator for PasswordAuthenticator {
         return Ok(true);
     }
     /// Logs out
-    async fn logout(&mut self, client: &Client, user_agent: &str) -> Result<(), Error> {
-        let url = "https://www.reddit.com/api/v1/revoke_token";
-        let body = format!("token={}", &self.token.to_owned().unwrap());
+    async fn logout(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<(), Error> {
+        let token = match self.token.as_ref() {
+            Some(token) => token,
+            // No token to revoke
+            None => return Ok(()),
+        };
+        let url = endpoints.revoke_token_url();
+        let body = Params::new().with("token", token);
 
         let mut header = HeaderMap::new();
         header.insert(USER_AGENT, HeaderValue::from_str(user_agent).unwrap());
@@ -116,14 +137,19 @@ impl Authenticator for PasswordAuthenticator {
             .headers(header)
             .send()
             .await?;
-        response.status().into_result()?;
+        check_status(Method::POST, response).await?;
         self.token = None;
         self.expiration_time = None;
         Ok(())
     }
     /// Returns true if successful
-    async fn token_refresh(&mut self, client: &Client, user_agent: &str) -> Result<bool, Error> {
-        self.login(client, user_agent).await
+    async fn token_refresh(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        self.login(client, user_agent, endpoints).await
     }
     /// headers
     fn headers(&self, headers: &mut HeaderMap) {
@@ -140,22 +166,17 @@ impl Authenticator for PasswordAuthenticator {
     fn oauth(&self) -> bool {
         true
     }
-    /// Validates Time
-    fn needs_token_refresh(&self) -> bool {
-        let i = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
-        if self.expiration_time.is_none() {
-            true
-        } else {
-            i >= self.expiration_time.unwrap()
-        }
+    /// True if the token expires within the margin
+    fn needs_token_refresh(&self, margin: Duration) -> bool {
+        expires_within(self.expiration_time, margin)
     }
     /// Always None
     fn get_refresh_token(&self) -> Option<String> {
         Option::None
     }
+    fn scopes(&self) -> Option<Vec<Scope>> {
+        self.scopes.clone()
+    }
 }
 
 impl Authorized for PasswordAuthenticator {}
@@ -0,0 +1,186 @@
+//! The OAuth scopes Reddit grants and the scope each endpoint requires
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use reqwest::Method;
+
+use crate::error::Error;
+
+/// An OAuth scope. [Reddit's list](https://www.reddit.com/api/v1/scopes)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Account,
+    Creddits,
+    Edit,
+    Flair,
+    History,
+    Identity,
+    LiveManage,
+    ModConfig,
+    ModContributors,
+    ModFlair,
+    ModLog,
+    ModMail,
+    ModOthers,
+    ModPosts,
+    ModSelf,
+    ModTraffic,
+    ModWiki,
+    MySubreddits,
+    PrivateMessages,
+    Read,
+    Report,
+    Save,
+    StructuredStyles,
+    Submit,
+    Subscribe,
+    Vote,
+    WikiEdit,
+    WikiRead,
+    /// `*`. Every scope. Granted to script apps using the password grant
+    All,
+}
+
+impl Scope {
+    /// The name Reddit uses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Account => "account",
+            Scope::Creddits => "creddits",
+            Scope::Edit => "edit",
+            Scope::Flair => "flair",
+            Scope::History => "history",
+            Scope::Identity => "identity",
+            Scope::LiveManage => "livemanage",
+            Scope::ModConfig => "modconfig",
+            Scope::ModContributors => "modcontributors",
+            Scope::ModFlair => "modflair",
+            Scope::ModLog => "modlog",
+            Scope::ModMail => "modmail",
+            Scope::ModOthers => "modothers",
+            Scope::ModPosts => "modposts",
+            Scope::ModSelf => "modself",
+            Scope::ModTraffic => "modtraffic",
+            Scope::ModWiki => "modwiki",
+            Scope::MySubreddits => "mysubreddits",
+            Scope::PrivateMessages => "privatemessages",
+            Scope::Read => "read",
+            Scope::Report => "report",
+            Scope::Save => "save",
+            Scope::StructuredStyles => "structuredstyles",
+            Scope::Submit => "submit",
+            Scope::Subscribe => "subscribe",
+            Scope::Vote => "vote",
+            Scope::WikiEdit => "wikiedit",
+            Scope::WikiRead => "wikiread",
+            Scope::All => "*",
+        }
+    }
+    /// Parses the `scope` of a token response. Reddit separates them by spaces or commas.
+    ///
+    /// Scopes this crate does not know are skipped
+    /// ```rust
+    /// use rraw::auth::Scope;
+    /// assert_eq!(Scope::parse_granted("identity read,submit"), vec![Scope::Identity, Scope::Read, Scope::Submit]);
+    /// ```
+    pub fn parse_granted(scope: &str) -> Vec<Scope> {
+        scope
+            .split([' ', ','])
+            .filter(|scope| !scope.is_empty())
+            .filter_map(|scope| scope.parse().ok())
+            .collect()
+    }
+    /// True if the granted scopes include this one
+    pub fn granted_by(&self, granted: &[Scope]) -> bool {
+        granted.contains(&Scope::All) || granted.contains(self)
+    }
+    /// The scope Reddit requires for the endpoint. The path can include a query
+    ///
+    /// None for endpoints this crate does not know. Those are sent without a scope check
+    pub fn required_for(method: &Method, path: &str) -> Option<Scope> {
+        let path = path.split('?').next().unwrap_or_default();
+        let path = path.trim_end_matches('/').trim_end_matches(".json");
+        let segments: Vec<&str> = path.split('/').skip(1).collect();
+        let scope = match (method.as_str(), segments.as_slice()) {
+            ("GET", ["api", "v1", "me"]) => Scope::Identity,
+            ("GET", ["api", "v1", "me", "karma"]) => Scope::MySubreddits,
+            ("GET", ["api", "v1", "me", ..]) => Scope::Identity,
+            ("GET", ["message", ..]) => Scope::PrivateMessages,
+            ("GET", ["user", _, "saved" | "upvoted" | "downvoted" | "hidden"]) => Scope::History,
+            ("GET", ["user", _, "comments" | "submitted" | "overview" | "gilded"]) => {
+                Scope::History
+            }
+            ("GET", ["r", _, "about", "log"]) => Scope::ModLog,
+            (
+                "GET",
+                ["r", _, "about", "reports" | "spam" | "modqueue" | "unmoderated" | "edited"],
+            ) => Scope::ModPosts,
+            ("GET", ["r", _, "about"]) => Scope::Read,
+            ("GET", ["r", _, "about", "contributors" | "moderators"]) => Scope::Read,
+            ("GET", ["r", _, "hot" | "new" | "top" | "rising" | "controversial" | "random"]) => {
+                Scope::Read
+            }
+            ("GET", ["r", _, "comments", ..] | ["comments", ..]) => Scope::Read,
+            ("GET", ["domain", _, ..]) => Scope::Read,
+            ("GET", ["u" | "user", _, "about"]) => Scope::Read,
+            ("GET", ["subreddits" | "users", "search"]) => Scope::Read,
+            ("GET", ["api", "morechildren" | "info"]) => Scope::Read,
+            ("POST", ["api", "compose" | "block" | "del_msg"]) => Scope::PrivateMessages,
+            ("POST", ["api", "read_message" | "unread_message"]) => Scope::PrivateMessages,
+            ("POST", ["api", "submit" | "comment"]) => Scope::Submit,
+            ("POST", ["api", "editusertext" | "del"]) => Scope::Edit,
+            ("POST", ["api", "vote"]) => Scope::Vote,
+            ("POST", ["api", "save" | "unsave"]) => Scope::Save,
+            ("POST", ["api", "report" | "hide" | "unhide"]) => Scope::Report,
+            ("POST", ["api", "subscribe"]) => Scope::Subscribe,
+            _ => return None,
+        };
+        Some(scope)
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Scope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let scope = match s {
+            "account" => Scope::Account,
+            "creddits" => Scope::Creddits,
+            "edit" => Scope::Edit,
+            "flair" => Scope::Flair,
+            "history" => Scope::History,
+            "identity" => Scope::Identity,
+            "livemanage" => Scope::LiveManage,
+            "modconfig" => Scope::ModConfig,
+            "modcontributors" => Scope::ModContributors,
+            "modflair" => Scope::ModFlair,
+            "modlog" => Scope::ModLog,
+            "modmail" => Scope::ModMail,
+            "modothers" => Scope::ModOthers,
+            "modposts" => Scope::ModPosts,
+            "modself" => Scope::ModSelf,
+            "modtraffic" => Scope::ModTraffic,
+            "modwiki" => Scope::ModWiki,
+            "mysubreddits" => Scope::MySubreddits,
+            "privatemessages" => Scope::PrivateMessages,
+            "read" => Scope::Read,
+            "report" => Scope::Report,
+            "save" => Scope::Save,
+            "structuredstyles" => Scope::StructuredStyles,
+            "submit" => Scope::Submit,
+            "subscribe" => Scope::Subscribe,
+            "vote" => Scope::Vote,
+            "wikiedit" => Scope::WikiEdit,
+            "wikiread" => Scope::WikiRead,
+            "*" => Scope::All,
+            _ => return Err(Error::Other(format!("Unknown scope {}", s))),
+        };
+        Ok(scope)
+    }
+}
@@ -0,0 +1,182 @@
+//! Keeps the tokens of an Authenticator between restarts and between processes
+use std::fmt::Debug;
+#[cfg(feature = "tokio")]
+use std::io::ErrorKind;
+#[cfg(feature = "tokio")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "tokio")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tokio")]
+use tokio::io::AsyncWriteExt;
+
+use crate::auth::expires_within;
+#[cfg(feature = "tokio")]
+use crate::error::internal_error::InternalError;
+use crate::error::Error;
+
+/// The token state of an Authenticator
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct StoredToken {
+    /// Access token
+    pub token: Option<String>,
+    /// When does it expire. Milliseconds since the Unix Epoch
+    pub expiration_time: Option<u128>,
+    /// Refresh token
+    pub refresh_token: Option<String>,
+    /// The granted scopes. Separated by spaces
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+impl StoredToken {
+    /// True if there is no access token or it has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_within(Duration::ZERO)
+    }
+    /// True if there is no access token or it expires within the margin.
+    /// The same rule [Authenticator::needs_token_refresh](crate::auth::Authenticator::needs_token_refresh) uses
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        self.token.is_none() || expires_within(self.expiration_time, margin)
+    }
+}
+
+/// Where an Authenticator loads and saves its tokens.
+///
+/// `load` is called before logging in and refreshing. `save` is called after Reddit issued a new token.
+/// `clear` is called on logout
+#[async_trait(?Send)]
+pub trait TokenStore: Send + Sync + Debug {
+    /// The saved tokens. None if nothing was saved yet
+    async fn load(&self) -> Result<Option<StoredToken>, Error>;
+    /// Saves the tokens
+    async fn save(&self, token: &StoredToken) -> Result<(), Error>;
+    /// Forgets the saved tokens
+    async fn clear(&self) -> Result<(), Error>;
+}
+
+/// Keeps the tokens in memory. Clones share the same tokens
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTokenStore {
+    token: Arc<Mutex<Option<StoredToken>>>,
+}
+
+impl MemoryTokenStore {
+    /// Creates an empty store
+    pub fn new() -> MemoryTokenStore {
+        MemoryTokenStore::default()
+    }
+}
+
+#[async_trait(?Send)]
+impl TokenStore for MemoryTokenStore {
+    async fn load(&self) -> Result<Option<StoredToken>, Error> {
+        Ok(self.token.lock().unwrap().clone())
+    }
+
+    async fn save(&self, token: &StoredToken) -> Result<(), Error> {
+        *self.token.lock().unwrap() = Some(token.clone());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), Error> {
+        *self.token.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+/// Keeps the tokens in a JSON file. Requires the `tokio` dependency. `shared_authentication` enables it
+///
+/// The tokens are written to a temporary file that replaces the file with a rename.
+/// So a crash or another process never sees a partially written file.
+/// On unix the file is only readable by its owner
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() ->anyhow::Result<()>{
+///    use std::env;
+///    use rraw::auth::{FileTokenStore, TokenAuthenticator};
+///    use rraw::Client;
+///    let auth = TokenAuthenticator::new(env::var("CLIENT_ID")?, env::var("CLIENT_SECRET")?, env::var("REFRESH_TOKEN")?)
+///        .with_store(FileTokenStore::new("reddit_token.json"));
+///    let client = Client::login(auth, "RRAW Test (by u/KingTuxWH)").await?;
+///    Ok(())
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+#[cfg(feature = "tokio")]
+impl FileTokenStore {
+    /// The file does not need to exist yet
+    pub fn new<P: Into<PathBuf>>(path: P) -> FileTokenStore {
+        FileTokenStore { path: path.into() }
+    }
+    /// The location of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Tells apart the temporary files of saves running at the same time
+#[cfg(feature = "tokio")]
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "tokio")]
+#[async_trait(?Send)]
+impl TokenStore for FileTokenStore {
+    async fn load(&self) -> Result<Option<StoredToken>, Error> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(InternalError::IOError(error).into()),
+        }
+    }
+
+    async fn save(&self, token: &StoredToken) -> Result<(), Error> {
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            TEMP_FILES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let json = serde_json::to_vec_pretty(token)?;
+        let written = write_private(Path::new(&temp), &json).await;
+        let renamed = match written {
+            Ok(()) => tokio::fs::rename(&temp, &self.path).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = renamed {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(InternalError::IOError(error).into());
+        }
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), Error> {
+        match tokio::fs::remove_file(&self.path).await {
+            Err(error) if error.kind() != ErrorKind::NotFound => {
+                Err(InternalError::IOError(error).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Creates the file only readable by its owner on unix. Then writes and syncs the bytes
+#[cfg(feature = "tokio")]
+async fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    file.write_all(bytes).await?;
+    file.sync_all().await
+}
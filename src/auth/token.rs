@@ -1,16 +1,22 @@
 use std::fmt::{Debug, Formatter};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::auth::{CodeAuthenticator, TokenResponseData, AUTH_CONTENT_TYPE};
-use crate::{Authenticator, Authorized, utils};
+use crate::auth::{
+    expires_within, CodeAuthenticator, Scope, StoredToken, TokenResponseData, TokenStore,
+    AUTH_CONTENT_TYPE, DEFAULT_REFRESH_MARGIN,
+};
+use crate::{utils, Authenticator, Authorized};
 use async_trait::async_trait;
 use log::warn;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
-use reqwest::{Body, Client};
+use reqwest::{Body, Client, Method};
 
-use crate::error::http_error::IntoResult;
+use crate::builder::Endpoints;
+use crate::error::http_error::check_status;
 use crate::error::internal_error::InternalError;
 use crate::error::Error;
+use crate::utils::request::Params;
 
 #[derive(Clone)]
 pub struct TokenAuthenticator {
@@ -20,10 +26,16 @@ pub struct TokenAuthenticator {
     pub expiration_time: Option<u128>,
     /// Refresh token
     pub refresh_token: String,
+    /// The granted scopes
+    scopes: Option<Vec<Scope>>,
     /// Client ID
     client_id: String,
     /// Client Secret
     client_secret: String,
+    /// Where the tokens are kept between restarts
+    store: Option<Arc<dyn TokenStore>>,
+    /// Stored tokens that expire within it are not used
+    refresh_margin: Duration,
 }
 
 impl Debug for TokenAuthenticator {
@@ -50,27 +62,78 @@ impl TokenAuthenticator {
         TokenAuthenticator {
             token: None,
             expiration_time: None,
+            scopes: None,
             refresh_token: refresh_token.into(),
             client_id: client_id.into(),
             client_secret: client_secret.into(),
+            store: None,
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
         }
     }
-}
-
-#[async_trait(?Send)]
-impl Authenticator for TokenAuthenticator {
-    /// Logs in
-    async fn login(&mut self, client: &Client, user_agent: &str) -> Result<bool, Error> {
-        let url = "https://www.reddit.com/api/v1/access_token";
-        let body = format!(
-            "grant_type=refresh_token&refresh_token={}",
-            &self.refresh_token
-        );
+    /// Loads the tokens from the store before logging in and saves them after Reddit issues new ones
+    pub fn with_store<S: TokenStore + 'static>(mut self, store: S) -> TokenAuthenticator {
+        self.store = Some(Arc::new(store));
+        self
+    }
+    /// Takes the tokens of the store. Returns true if the stored access token differs from the current one
+    /// and is valid for longer than the refresh margin
+    async fn restore(&mut self) -> Result<bool, Error> {
+        let stored = match &self.store {
+            Some(store) => store.load().await?,
+            None => None,
+        };
+        let stored = match stored {
+            Some(stored) => stored,
+            None => return Ok(false),
+        };
+        if let Some(refresh_token) = stored.refresh_token.clone() {
+            self.refresh_token = refresh_token;
+        }
+        if stored.token == self.token || stored.expires_within(self.refresh_margin) {
+            return Ok(false);
+        }
+        self.token = stored.token;
+        self.expiration_time = stored.expiration_time;
+        self.scopes = stored.scope.as_deref().map(Scope::parse_granted);
+        Ok(true)
+    }
+    async fn persist(&self) -> Result<(), Error> {
+        if let Some(store) = &self.store {
+            let token = StoredToken {
+                token: self.token.clone(),
+                expiration_time: self.expiration_time,
+                scope: self.scopes.as_ref().map(|scopes| {
+                    scopes
+                        .iter()
+                        .map(Scope::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                }),
+                refresh_token: Some(self.refresh_token.clone()),
+            };
+            store.save(&token).await?;
+        }
+        Ok(())
+    }
+    /// Trades the refresh token for a new access token
+    async fn refresh_grant(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        if self.refresh_token.is_empty() {
+            return Err(Error::NoRefreshToken);
+        }
+        let url = endpoints.access_token_url();
+        let body = Params::new()
+            .with("grant_type", "refresh_token")
+            .with("refresh_token", &self.refresh_token);
         let mut header = HeaderMap::new();
 
         header.insert(
             AUTHORIZATION,
-            HeaderValue::from_str(&*format!(
+            HeaderValue::from_str(&format!(
                 "Basic {}",
                 utils::basic_header(&self.client_id, &self.client_secret)
             ))
@@ -88,9 +151,10 @@ impl Authenticator for TokenAuthenticator {
             .send()
             .await
             .map_err(InternalError::from)?;
-        response.status().into_result()?;
+        let response = check_status(Method::POST, response).await?;
 
         let token: TokenResponseData = response.json().await?;
+        self.scopes = Some(Scope::parse_granted(&token.scope));
         self.token = Some(token.access_token);
         let x = token.expires_in * 1000;
         let x1 = (x as u128)
@@ -99,15 +163,36 @@ impl Authenticator for TokenAuthenticator {
                 .unwrap()
                 .as_millis();
         self.expiration_time = Some(x1);
-        return Ok(true);
+        self.persist().await?;
+        Ok(true)
+    }
+}
+
+#[async_trait(?Send)]
+impl Authenticator for TokenAuthenticator {
+    /// Logs in
+    async fn login(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        if self.token.is_none() && self.restore().await? {
+            return Ok(true);
+        }
+        self.refresh_grant(client, user_agent, endpoints).await
     }
     /// Logs out
-    async fn logout(&mut self, client: &Client, user_agent: &str) -> Result<(), Error> {
-        let url = "https://www.reddit.com/api/v1/revoke_token";
-        let body = format!(
-            "token={}&token_type_hint=refresh_token",
-            &self.refresh_token.to_owned()
-        );
+    async fn logout(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<(), Error> {
+        let url = endpoints.revoke_token_url();
+        let body = Params::new()
+            .with("token", &self.refresh_token)
+            .with("token_type_hint", "refresh_token");
 
         let mut header = HeaderMap::new();
         header.insert(USER_AGENT, HeaderValue::from_str(user_agent).unwrap());
@@ -118,15 +203,27 @@ impl Authenticator for TokenAuthenticator {
             .headers(header)
             .send()
             .await?;
-        response.status().into_result()?;
+        check_status(Method::POST, response).await?;
         self.token = None;
         self.expiration_time = None;
         self.refresh_token = "".to_string();
+        if let Some(store) = &self.store {
+            store.clear().await?;
+        }
         Ok(())
     }
     /// Returns true if successful
-    async fn token_refresh(&mut self, client: &Client, user_agent: &str) -> Result<bool, Error> {
-        self.login(client, user_agent).await
+    async fn token_refresh(
+        &mut self,
+        client: &Client,
+        user_agent: &str,
+        endpoints: &Endpoints,
+    ) -> Result<bool, Error> {
+        // Another worker sharing the store might have refreshed it already
+        if self.restore().await? {
+            return Ok(true);
+        }
+        self.refresh_grant(client, user_agent, endpoints).await
     }
     /// headers
     fn headers(&self, headers: &mut HeaderMap) {
@@ -143,24 +240,27 @@ impl Authenticator for TokenAuthenticator {
     fn oauth(&self) -> bool {
         true
     }
-    /// Validates Time
-    fn needs_token_refresh(&self) -> bool {
-        let i = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
-        if !self.refresh_token.is_empty() {
-            if self.expiration_time.is_none() {
-                true
-            } else {
-                i >= self.expiration_time.unwrap()
-            }
+    /// True if the token expires within the margin. Without a refresh token only once it expired
+    fn needs_token_refresh(&self, margin: Duration) -> bool {
+        if self.refresh_token.is_empty() {
+            expires_within(self.expiration_time, Duration::ZERO)
         } else {
-            false
+            expires_within(self.expiration_time, margin)
         }
     }
+    /// None after the refresh token was revoked by logging out
     fn get_refresh_token(&self) -> Option<String> {
-        Some(self.refresh_token.to_owned())
+        if self.refresh_token.is_empty() {
+            None
+        } else {
+            Some(self.refresh_token.to_owned())
+        }
+    }
+    fn scopes(&self) -> Option<Vec<Scope>> {
+        self.scopes.clone()
+    }
+    fn set_refresh_margin(&mut self, margin: Duration) {
+        self.refresh_margin = margin;
     }
 }
 
@@ -171,14 +271,17 @@ impl TryInto<TokenAuthenticator> for CodeAuthenticator {
 
     fn try_into(self) -> Result<TokenAuthenticator, Self::Error> {
         if self.refresh_token.is_none() {
-            return Err(Error::Other("No Refresh Token Provided".to_owned()));
+            return Err(Error::NoRefreshToken);
         }
         Ok(TokenAuthenticator {
             token: self.token,
             expiration_time: self.expiration_time,
+            scopes: self.scopes,
             refresh_token: self.refresh_token.unwrap(),
             client_id: self.client_id,
             client_secret: self.client_secret,
+            store: self.store,
+            refresh_margin: self.refresh_margin,
         })
     }
 }
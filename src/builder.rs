@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use reqwest::ClientBuilder as ReqwestClientBuilder;
+
+use crate::auth::{Authenticator, DEFAULT_REFRESH_MARGIN};
+use crate::error::Error;
+use crate::retry::RetryPolicy;
+use crate::Client;
+
+/// The Reddit hosts the Client talks to.
+///
+/// Defaults to the real Reddit API. Override it to point the Client at a local stand-in server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Endpoints {
+    /// Used for the private API. Default: `https://reddit.com`
+    pub reddit: String,
+    /// Used for OAuth requests. Default: `https://oauth.reddit.com`
+    pub oauth: String,
+    /// Used for non OAuth requests. Default: `https://api.reddit.com`
+    pub api: String,
+    /// Used to obtain and revoke tokens. Default: `https://www.reddit.com`
+    pub auth: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints {
+            reddit: "https://reddit.com".to_string(),
+            oauth: "https://oauth.reddit.com".to_string(),
+            api: "https://api.reddit.com".to_string(),
+            auth: "https://www.reddit.com".to_string(),
+        }
+    }
+}
+
+impl Endpoints {
+    /// Points every endpoint at the same host. Useful for a local mock server
+    /// ```rust
+    /// use rraw::builder::Endpoints;
+    /// let endpoints = Endpoints::with_base("http://127.0.0.1:8080/");
+    /// assert_eq!(endpoints.access_token_url(), "http://127.0.0.1:8080/api/v1/access_token");
+    /// ```
+    pub fn with_base<S: Into<String>>(base: S) -> Endpoints {
+        let base = base.into().trim_end_matches('/').to_string();
+        Endpoints {
+            reddit: base.clone(),
+            oauth: base.clone(),
+            api: base.clone(),
+            auth: base,
+        }
+    }
+    /// The page users authorize an app on
+    pub fn authorize_url(&self) -> String {
+        format!("{}/api/v1/authorize", self.reddit)
+    }
+    /// The URL used to obtain an access token
+    pub fn access_token_url(&self) -> String {
+        format!("{}/api/v1/access_token", self.auth)
+    }
+    /// The URL used to revoke a token
+    pub fn revoke_token_url(&self) -> String {
+        format!("{}/api/v1/revoke_token", self.auth)
+    }
+}
+
+/// Configures and creates a [Client]
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() ->anyhow::Result<()>{
+///    use rraw::auth::AnonymousAuthenticator;
+///    use rraw::builder::{ClientBuilder, Endpoints};
+///    let client = ClientBuilder::new("RRAW Test (by u/KingTuxWH)")
+///         .endpoints(Endpoints::with_base("http://127.0.0.1:8080"))
+///         .login(AnonymousAuthenticator::new())
+///         .await?;
+///    Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientBuilder {
+    pub(crate) user_agent: String,
+    pub(crate) endpoints: Endpoints,
+    pub(crate) wait_on_rate_limit: bool,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) lenient: bool,
+    pub(crate) refresh_margin: Duration,
+    pub(crate) replay_non_idempotent: bool,
+}
+
+impl ClientBuilder {
+    /// Creates a new ClientBuilder with the default Reddit endpoints
+    pub fn new<S: Into<String>>(user_agent: S) -> ClientBuilder {
+        ClientBuilder {
+            user_agent: user_agent.into(),
+            endpoints: Endpoints::default(),
+            wait_on_rate_limit: false,
+            retry_policy: RetryPolicy::default(),
+            lenient: false,
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
+            replay_non_idempotent: false,
+        }
+    }
+    /// Overrides the Reddit endpoints
+    pub fn endpoints(mut self, endpoints: Endpoints) -> ClientBuilder {
+        self.endpoints = endpoints;
+        self
+    }
+    /// If the Rate Limit is used up. Wait for it to reset before sending the next request.
+    ///
+    /// Default: false
+    pub fn wait_on_rate_limit(mut self, wait: bool) -> ClientBuilder {
+        self.wait_on_rate_limit = wait;
+        self
+    }
+    /// How transient failures are retried. Use [RetryPolicy::none] to disable retrying
+    ///
+    /// Default: [RetryPolicy::default]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> ClientBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+    /// Keep data with a kind rraw does not know as
+    /// [RedditTypeResponse::Unknown](crate::responses::RedditTypeResponse::Unknown) instead of failing.
+    /// Responses are decoded as [Lenient](crate::responses::Lenient)
+    ///
+    /// Default: false
+    pub fn lenient(mut self, lenient: bool) -> ClientBuilder {
+        self.lenient = lenient;
+        self
+    }
+    /// How long before the token expires it is refreshed
+    ///
+    /// Default: 60 seconds
+    pub fn refresh_margin(mut self, margin: Duration) -> ClientBuilder {
+        self.refresh_margin = margin;
+        self
+    }
+    /// When Reddit rejects the token with a 401 the Client refreshes it and sends the request again.
+    /// Only enable this for non-idempotent requests such as POST if the endpoints you call are safe to send twice
+    ///
+    /// Default: false
+    pub fn replay_non_idempotent(mut self, replay: bool) -> ClientBuilder {
+        self.replay_non_idempotent = replay;
+        self
+    }
+
+    /// Creates the Client. Complete Initial Login Steps
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn login<A: Authenticator>(self, mut auth: A) -> Result<Client<A>, Error> {
+        let client = ReqwestClientBuilder::new()
+            .user_agent(self.user_agent.clone())
+            .build()?;
+        let b = auth.oauth();
+        auth.set_refresh_margin(self.refresh_margin);
+        auth.login(&client, &self.user_agent, &self.endpoints)
+            .await?;
+        let r_t = auth.get_refresh_token();
+        Ok(Client {
+            #[cfg(feature = "shared_authentication")]
+            auth: std::sync::Arc::new(tokio::sync::RwLock::new(auth)),
+            #[cfg(not(feature = "shared_authentication"))]
+            auth: crate::LocalAuthenticator(std::sync::RwLock::new(auth)),
+            client,
+            user_agent: self.user_agent,
+            endpoints: self.endpoints,
+            rate_limit: Default::default(),
+            wait_on_rate_limit: self.wait_on_rate_limit,
+            retry_policy: self.retry_policy,
+            lenient: self.lenient,
+            refresh_token: r_t,
+            refresh_margin: self.refresh_margin,
+            replay_non_idempotent: self.replay_non_idempotent,
+            oauth: b,
+        })
+    }
+}
@@ -1,9 +1,12 @@
 pub mod response;
+pub mod tree;
 
 use crate::auth::Authenticator;
+use crate::comments::tree::CommentTree;
 use crate::utils::options::CommentOption;
 use crate::Client;
 use async_trait::async_trait;
+use serde_json::Value;
 
 use crate::error::Error;
 use crate::responses::listing::{GenericListing, ListingArray};
@@ -35,6 +38,12 @@ pub type Comments<'a, A, T> = GenericListing<Comment<'a, A, T>>;
 #[async_trait(?Send)]
 pub trait CommentRetriever {
     async fn get_comments(&self, sort: Option<CommentOption>) -> Result<ListingArray, Error>;
+    /// The comments as a [CommentTree]. Supports `more` stubs
+    ///
+    /// The default builds the tree from [CommentRetriever::get_comments]
+    async fn get_comment_tree(&self, sort: Option<CommentOption>) -> Result<CommentTree, Error> {
+        CommentTree::from_listings(self.get_comments(sort).await?)
+    }
 }
 #[async_trait(?Send)]
 impl<'a, A: Authenticator, T: CommentType<'a>> CommentRetriever for Comment<'a, A, T> {
@@ -45,4 +54,25 @@ impl<'a, A: Authenticator, T: CommentType<'a>> CommentRetriever for Comment<'a,
         }
         return self.me.get_json::<ListingArray>(&path, false, false).await;
     }
+    async fn get_comment_tree(&self, sort: Option<CommentOption>) -> Result<CommentTree, Error> {
+        self.me
+            .comment_tree(self.comment.get_permalink(), sort)
+            .await
+    }
+}
+
+impl<A: Authenticator> Client<A> {
+    /// Gets the comments of the permalink as a [CommentTree]
+    pub async fn comment_tree(
+        &self,
+        permalink: &str,
+        sort: Option<CommentOption>,
+    ) -> Result<CommentTree, Error> {
+        let mut path = permalink.to_string();
+        if let Some(options) = sort {
+            options.extend(&mut path)
+        }
+        let response = self.get_json::<Value>(&path, false, false).await?;
+        CommentTree::from_response(response)
+    }
 }
@@ -1,6 +1,11 @@
+use crate::comments::tree::MoreChildren;
 use crate::comments::CommentType;
-use crate::responses::listing::GenericListing;
-use serde::Deserialize;
+use crate::responses::listing::{GenericListing, Listing};
+use crate::responses::reports::{deserialize_reports, ModReport, UserReport};
+use crate::responses::{FullName, GenericResponse, HasFullName, RedditDataType};
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
 use std::fmt::{Debug, Formatter};
 
 #[derive(Deserialize)]
@@ -24,6 +29,116 @@ pub struct CommentResponse {
     pub distinguished: Option<String>,
     pub stickied: Option<bool>,
     pub ups: Option<i32>,
+    /// The number of reports. Only visible to moderators
+    pub num_reports: Option<i64>,
+    #[serde(default, deserialize_with = "deserialize_reports")]
+    pub mod_reports: Vec<ModReport>,
+    #[serde(default, deserialize_with = "deserialize_reports")]
+    pub user_reports: Vec<UserReport>,
+    /// The replies Reddit included. None when there are none.
+    ///
+    /// Use [CommentTree](crate::comments::tree::CommentTree) to expand the `more` stubs
+    #[serde(default, deserialize_with = "deserialize_replies")]
+    pub replies: Option<Listing<CommentOrMore>>,
+}
+
+/// A reply. Reddit sends `more` stubs for replies it left out
+#[derive(Deserialize, Debug)]
+#[serde(tag = "kind", content = "data")]
+pub enum CommentOrMore {
+    /// Type: `t1`
+    #[serde(rename = "t1")]
+    Comment(Box<CommentResponse>),
+    /// Type: `more`
+    #[serde(rename = "more")]
+    More(MoreChildren),
+}
+
+impl CommentOrMore {
+    /// The comment. None for `more` stubs
+    pub fn comment(&self) -> Option<&CommentResponse> {
+        match self {
+            CommentOrMore::Comment(comment) => Some(comment),
+            CommentOrMore::More(_) => None,
+        }
+    }
+    /// The `more` stub. None for comments
+    pub fn more(&self) -> Option<&MoreChildren> {
+        match self {
+            CommentOrMore::Comment(_) => None,
+            CommentOrMore::More(more) => Some(more),
+        }
+    }
+}
+
+/// Reddit sends `""` instead of a Listing when there are no replies
+fn deserialize_replies<'de, D>(deserializer: D) -> Result<Option<Listing<CommentOrMore>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(replies) if replies.is_empty() => Ok(None),
+        replies @ Value::Object(_) => {
+            serde_json::from_value::<GenericResponse<Listing<CommentOrMore>>>(replies)
+                .map(|replies| Some(replies.data))
+                .map_err(DeError::custom)
+        }
+        replies => Err(DeError::custom(format!(
+            "Expected \"\" or a Listing for the replies. Got {replies}"
+        ))),
+    }
+}
+
+impl CommentResponse {
+    /// The direct replies
+    pub fn children(&self) -> impl Iterator<Item = &CommentResponse> + '_ {
+        self.replies
+            .iter()
+            .flat_map(|listing| listing.children.iter())
+            .filter_map(CommentOrMore::comment)
+    }
+    /// The `more` stubs of the direct replies
+    pub fn more(&self) -> impl Iterator<Item = &MoreChildren> + '_ {
+        self.replies
+            .iter()
+            .flat_map(|listing| listing.children.iter())
+            .filter_map(CommentOrMore::more)
+    }
+    /// The comment and every reply below it. Depth first.
+    /// The depth is relative to this comment, which has a depth of 0
+    pub fn walk(&self) -> Walk<'_> {
+        Walk {
+            stack: vec![(0, self)],
+        }
+    }
+    /// The comment and every reply below it as a list. Depth first
+    pub fn flatten(&self) -> Vec<&CommentResponse> {
+        self.walk().map(|(_, comment)| comment).collect()
+    }
+    /// Finds the comment or a reply by its id or FullName
+    pub fn find(&self, id: &str) -> Option<&CommentResponse> {
+        let id = id.strip_prefix("t1_").unwrap_or(id);
+        self.walk()
+            .map(|(_, comment)| comment)
+            .find(|comment| comment.id == id)
+    }
+}
+
+/// Depth first iterator over a comment and its replies. Created with [CommentResponse::walk]
+pub struct Walk<'a> {
+    stack: Vec<(u32, &'a CommentResponse)>,
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = (u32, &'a CommentResponse);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, comment) = self.stack.pop()?;
+        let children: Vec<_> = comment.children().collect();
+        self.stack
+            .extend(children.into_iter().rev().map(|child| (depth + 1, child)));
+        Some((depth, comment))
+    }
 }
 impl Debug for CommentResponse {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -35,4 +150,12 @@ impl<'a> CommentType<'a> for CommentResponse {
         &self.permalink
     }
 }
+impl HasFullName for CommentResponse {
+    fn full_name(&self) -> FullName {
+        FullName {
+            reddit_type: RedditDataType::Comment,
+            id: self.id.clone(),
+        }
+    }
+}
 pub type CommentsResponse = GenericListing<CommentResponse>;
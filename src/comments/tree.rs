@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+
+use log::{debug, trace};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::auth::Authenticator;
+use crate::comments::response::{CommentOrMore, CommentResponse};
+use crate::error::reddit_error::RedditError;
+use crate::error::Error;
+use crate::responses::api::APIResponse;
+use crate::responses::listing::ListingArray;
+use crate::responses::RedditTypeResponse;
+use crate::utils::request::ApiRequest;
+use crate::Client;
+
+/// The most children `/api/morechildren` accepts in one request
+pub const MORE_CHILDREN_LIMIT: usize = 100;
+
+/// A `more` stub. Reddit uses it for comments that were not included in the response
+#[derive(Deserialize, Debug, Clone)]
+pub struct MoreChildren {
+    /// How many comments are hidden behind the stub
+    #[serde(default)]
+    pub count: u64,
+    /// The FullName of the stub
+    pub name: String,
+    pub id: String,
+    /// The FullName of the parent comment or submission
+    pub parent_id: String,
+    #[serde(default)]
+    pub depth: u32,
+    /// The ids of the hidden comments.
+    /// Empty for "continue this thread" stubs. Those can not be expanded with `/api/morechildren`
+    #[serde(default)]
+    pub children: Vec<String>,
+}
+
+impl MoreChildren {
+    /// If the stub can be expanded with `/api/morechildren`
+    pub fn is_expandable(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+/// The data of a node in the [CommentTree]
+#[derive(Debug)]
+pub enum CommentNodeKind {
+    /// A Comment
+    Comment(Box<CommentResponse>),
+    /// A `more` stub
+    More(MoreChildren),
+    /// A `more` stub that was expanded. It is no longer part of the tree
+    Expanded,
+}
+
+/// The index of a node inside its [CommentTree]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// A node in the [CommentTree]
+#[derive(Debug)]
+pub struct CommentNode {
+    pub kind: CommentNodeKind,
+    /// Top level comments have a depth of 0
+    pub depth: u32,
+    /// None for top level comments
+    pub parent: Option<NodeId>,
+    pub children: Vec<NodeId>,
+}
+
+impl CommentNode {
+    /// The comment. None for `more` stubs
+    pub fn comment(&self) -> Option<&CommentResponse> {
+        match &self.kind {
+            CommentNodeKind::Comment(comment) => Some(comment),
+            _ => None,
+        }
+    }
+    /// The `more` stub. None for comments
+    pub fn more(&self) -> Option<&MoreChildren> {
+        match &self.kind {
+            CommentNodeKind::More(more) => Some(more),
+            _ => None,
+        }
+    }
+}
+
+/// The comments of a submission as a tree.
+///
+/// Nodes are stored in the tree and linked by [NodeId]. Use [CommentTree::expand_more] to
+/// replace the `more` stubs with the comments they hide.
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() ->anyhow::Result<()>{
+///    use rraw::auth::AnonymousAuthenticator;
+///    use rraw::comments::CommentRetriever;
+///    use rraw::submission::{SubmissionRetriever, SubmissionType};
+///    use rraw::Client;
+///    let client = Client::login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
+///    let subreddit = client.subreddit("rust").await?;
+///    let hot = subreddit.hot(None).await?;
+///    let submission = hot.data.children[0].data.to_submission(&client);
+///    let mut tree = submission.get_comment_tree(None).await?;
+///    tree.expand_more(&client, 5).await?;
+///    for id in tree.iter() {
+///        if let Some(comment) = tree.get(id).comment() {
+///            println!("{}{}", "  ".repeat(tree.get(id).depth as usize), comment.body);
+///        }
+///    }
+///    Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CommentTree {
+    /// The FullName of the submission
+    pub link_id: String,
+    nodes: Vec<CommentNode>,
+    roots: Vec<NodeId>,
+    by_name: HashMap<String, NodeId>,
+}
+
+impl CommentTree {
+    /// Builds the tree from the `[submission, comments]` array Reddit responds with
+    pub fn from_response(response: Value) -> Result<CommentTree, Error> {
+        let mut listings = match response {
+            Value::Array(listings) if listings.len() == 2 => listings,
+            _ => return Err(Error::from("Expected a submission and a comment listing")),
+        };
+        let comments = listings.pop().unwrap_or_default();
+        let link_id = listings[0]["data"]["children"][0]["data"]["name"]
+            .as_str()
+            .ok_or_else(|| Error::from("The submission is missing its name"))?
+            .to_string();
+        let mut tree = CommentTree {
+            link_id,
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            by_name: HashMap::new(),
+        };
+        tree.insert_listing(comments, None)?;
+        Ok(tree)
+    }
+    /// Builds the tree from the deserialized `[submission, comments]` listings
+    pub fn from_listings(listings: ListingArray) -> Result<CommentTree, Error> {
+        let mut listings = listings.into_iter();
+        let (submission, comments) = match (listings.next(), listings.next(), listings.next()) {
+            (Some(submission), Some(comments), None) => (submission, comments),
+            _ => return Err(Error::from("Expected a submission and a comment listing")),
+        };
+        let link_id = match submission
+            .data
+            .children
+            .into_iter()
+            .next()
+            .map(|thing| thing.data)
+        {
+            Some(RedditTypeResponse::Link(submission)) => submission.name,
+            _ => return Err(Error::from("The submission is missing its name")),
+        };
+        let mut tree = CommentTree {
+            link_id,
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            by_name: HashMap::new(),
+        };
+        for thing in comments.data.children {
+            match thing.data {
+                RedditTypeResponse::Comment(comment) => tree.insert_comment(comment, None),
+                RedditTypeResponse::More(more) => {
+                    tree.push_node(CommentNodeKind::More(more), None, None);
+                }
+                _ => return Err(RedditError::InvalidDataType(thing.kind.to_string()).into()),
+            }
+        }
+        Ok(tree)
+    }
+    /// The top level nodes
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+    /// The node for the id. Expanded `more` stubs are kept as [CommentNodeKind::Expanded]
+    pub fn get(&self, id: NodeId) -> &CommentNode {
+        &self.nodes[id.0]
+    }
+    /// The parent of the node. None for top level nodes
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.get(id).parent
+    }
+    /// The children of the node
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.get(id).children
+    }
+    /// Finds a comment by its id or FullName
+    pub fn find(&self, id: &str) -> Option<NodeId> {
+        match self.by_name.get(id) {
+            Some(node) => Some(*node),
+            None => self.by_name.get(&format!("t1_{id}")).copied(),
+        }
+    }
+    /// Every node in the tree. Depth first, in the order Reddit sorted them
+    pub fn iter(&self) -> impl Iterator<Item = NodeId> + '_ {
+        let mut stack: Vec<NodeId> = self.roots.iter().rev().copied().collect();
+        std::iter::from_fn(move || {
+            let id = stack.pop()?;
+            stack.extend(self.children(id).iter().rev());
+            Some(id)
+        })
+    }
+    /// Every comment in the tree. Depth first
+    pub fn comments(&self) -> impl Iterator<Item = &CommentResponse> + '_ {
+        self.iter().filter_map(move |id| self.get(id).comment())
+    }
+    /// The `more` stubs still in the tree
+    pub fn more(&self) -> Vec<NodeId> {
+        self.iter()
+            .filter(|id| self.get(*id).more().is_some())
+            .collect()
+    }
+    /// The number of nodes in the tree
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+    /// If the tree has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Replaces `more` stubs with the comments they hide, using `/api/morechildren`.
+    ///
+    /// At most `max_requests` requests are made. Stubs that are returned by the expansion are
+    /// expanded as well while requests remain. "continue this thread" stubs are left alone.
+    /// Returns the number of requests made
+    pub async fn expand_more<A: Authenticator>(
+        &mut self,
+        client: &Client<A>,
+        max_requests: usize,
+    ) -> Result<usize, Error> {
+        let mut requests = 0;
+        while requests < max_requests {
+            let next = self.more().into_iter().find(|id| {
+                self.get(*id)
+                    .more()
+                    .map_or(false, MoreChildren::is_expandable)
+            });
+            let more_id = match next {
+                Some(id) => id,
+                None => break,
+            };
+            let chunk: Vec<String> = self
+                .get(more_id)
+                .more()
+                .map(|more| {
+                    more.children
+                        .iter()
+                        .take(MORE_CHILDREN_LIMIT)
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default();
+            trace!("Expanding {} comments", chunk.len());
+            let things = client.more_children(&self.link_id, &chunk).await?;
+            requests += 1;
+            self.insert_expanded(more_id, things)?;
+            let expanded = match &mut self.nodes[more_id.0].kind {
+                CommentNodeKind::More(more) => {
+                    more.children.drain(..chunk.len());
+                    more.count = more.count.saturating_sub(chunk.len() as u64);
+                    more.children.is_empty()
+                }
+                _ => true,
+            };
+            if expanded {
+                self.detach(more_id);
+            }
+        }
+        debug!("Expanded the comment tree with {} requests", requests);
+        Ok(requests)
+    }
+
+    fn insert_listing(&mut self, listing: Value, parent: Option<NodeId>) -> Result<(), Error> {
+        let children = match listing {
+            Value::Object(mut listing) => match listing.remove("data") {
+                Some(Value::Object(mut data)) => data.remove("children"),
+                _ => None,
+            },
+            // Reddit sends "" when there are no replies
+            _ => None,
+        };
+        if let Some(Value::Array(children)) = children {
+            for child in children {
+                self.insert_thing(child, parent, None)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Inserts a thing and its replies. Returns the NodeId
+    fn insert_thing(
+        &mut self,
+        thing: Value,
+        parent: Option<NodeId>,
+        position: Option<usize>,
+    ) -> Result<NodeId, Error> {
+        let (kind, mut data) = match thing {
+            Value::Object(mut thing) => (
+                thing.remove("kind").unwrap_or_default(),
+                thing.remove("data").unwrap_or_default(),
+            ),
+            _ => return Err(Error::from("Expected a comment or more stub")),
+        };
+        let replies = data.as_object_mut().and_then(|data| data.remove("replies"));
+        let kind = match kind.as_str() {
+            Some("t1") => CommentNodeKind::Comment(Box::new(serde_json::from_value(data)?)),
+            Some("more") => CommentNodeKind::More(serde_json::from_value(data)?),
+            Some(kind) => return Err(RedditError::InvalidDataType(kind.to_string()).into()),
+            None => return Err(Error::from("Some how we are missing a kind tag")),
+        };
+        let id = self.push_node(kind, parent, position);
+        if let Some(replies) = replies {
+            self.insert_listing(replies, Some(id))?;
+        }
+        Ok(id)
+    }
+
+    /// Inserts a deserialized comment and its replies
+    fn insert_comment(&mut self, mut comment: CommentResponse, parent: Option<NodeId>) {
+        let replies = comment.replies.take();
+        let id = self.push_node(CommentNodeKind::Comment(Box::new(comment)), parent, None);
+        for reply in replies.map(|replies| replies.children).unwrap_or_default() {
+            match reply {
+                CommentOrMore::Comment(reply) => self.insert_comment(*reply, Some(id)),
+                CommentOrMore::More(more) => {
+                    self.push_node(CommentNodeKind::More(more), Some(id), None);
+                }
+            }
+        }
+    }
+
+    /// Adds the node below the parent. At the end or at the position
+    fn push_node(
+        &mut self,
+        kind: CommentNodeKind,
+        parent: Option<NodeId>,
+        position: Option<usize>,
+    ) -> NodeId {
+        // Only comments are found by name. A stub has the name of the first comment it hides
+        let name = match &kind {
+            CommentNodeKind::Comment(comment) => Some(format!("t1_{}", comment.id)),
+            _ => None,
+        };
+        let id = NodeId(self.nodes.len());
+        let depth = parent.map_or(0, |parent| self.get(parent).depth + 1);
+        self.nodes.push(CommentNode {
+            kind,
+            depth,
+            parent,
+            children: Vec::new(),
+        });
+        let siblings = match parent {
+            Some(parent) => &mut self.nodes[parent.0].children,
+            None => &mut self.roots,
+        };
+        match position {
+            Some(position) => siblings.insert(position.min(siblings.len()), id),
+            None => siblings.push(id),
+        }
+        if let Some(name) = name {
+            self.by_name.insert(name, id);
+        }
+        id
+    }
+
+    /// Inserts the flat list of things from `/api/morechildren` where the stub is
+    fn insert_expanded(&mut self, more_id: NodeId, things: Vec<Value>) -> Result<(), Error> {
+        let stub_parent = self.get(more_id).parent;
+        let mut position = self
+            .siblings(stub_parent)
+            .iter()
+            .position(|id| *id == more_id);
+        for thing in things {
+            let parent_name = thing["data"]["parent_id"].as_str().unwrap_or_default();
+            let parent = if parent_name == self.link_id {
+                None
+            } else {
+                match self.by_name.get(parent_name) {
+                    Some(parent) => Some(*parent),
+                    None => {
+                        debug!("Skipping a comment with the unknown parent {}", parent_name);
+                        continue;
+                    }
+                }
+            };
+            if parent == stub_parent {
+                // Keep the new siblings where the stub was. In the order Reddit sent them
+                let id = self.insert_thing(thing, parent, position)?;
+                position = self.siblings(parent).iter().position(|node| *node == id);
+                position = position.map(|position| position + 1);
+            } else {
+                self.insert_thing(thing, parent, None)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn siblings(&self, parent: Option<NodeId>) -> &[NodeId] {
+        match parent {
+            Some(parent) => self.children(parent),
+            None => &self.roots,
+        }
+    }
+
+    /// Removes the expanded stub from its parent. It stays in storage as a tombstone
+    fn detach(&mut self, id: NodeId) {
+        self.nodes[id.0].kind = CommentNodeKind::Expanded;
+        let siblings = match self.nodes[id.0].parent {
+            Some(parent) => &mut self.nodes[parent.0].children,
+            None => &mut self.roots,
+        };
+        siblings.retain(|sibling| *sibling != id);
+    }
+}
+
+#[derive(Deserialize)]
+struct MoreChildrenData {
+    things: Vec<Value>,
+}
+
+impl<A: Authenticator> Client<A> {
+    /// Loads the comments hidden by a `more` stub. Returns the raw things.
+    /// At most [MORE_CHILDREN_LIMIT] children are accepted by Reddit
+    pub async fn more_children(
+        &self,
+        link_id: &str,
+        children: &[String],
+    ) -> Result<Vec<Value>, Error> {
+        let request = ApiRequest::new("/api/morechildren")
+            .query("api_type", "json")
+            .query("link_id", link_id)
+            .query("children", children.join(","))
+            .query("limit_children", false);
+        let response = self
+            .get_json::<APIResponse<MoreChildrenData>>(&request.url(), false, false)
+            .await?;
+        Ok(response
+            .into_result()?
+            .map(|data| data.things)
+            .unwrap_or_default())
+    }
+}
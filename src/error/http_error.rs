@@ -1,6 +1,14 @@
-use reqwest::StatusCode;
+use std::fmt::{Display, Formatter};
+
+use reqwest::header::{HeaderMap, HeaderName, RETRY_AFTER, WWW_AUTHENTICATE};
+use reqwest::{Method, Response, StatusCode};
 use thiserror::Error;
 
+use crate::rate_limit::{RATE_LIMIT_REMAINING, RATE_LIMIT_RESET, RATE_LIMIT_USED};
+
+/// The longest response body kept in an [ErrorResponse]
+pub const MAX_BODY_LENGTH: usize = 1024;
+
 pub trait IntoResult {
     fn into_result(self) -> Result<(), HTTPError>;
 }
@@ -15,12 +23,71 @@ impl IntoResult for StatusCode {
     }
 }
 
+/// What was sent and what Reddit responded with
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ErrorResponse {
+    pub status: StatusCode,
+    pub method: Method,
+    /// The path of the URL. Without the query
+    pub path: String,
+    /// The response body. Truncated to [MAX_BODY_LENGTH] bytes
+    pub body: String,
+    /// `Retry-After`, `WWW-Authenticate` and the rate limit headers
+    pub headers: HeaderMap,
+}
+
+impl Display for ErrorResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' from {} {}", self.status, self.method, self.path)?;
+        if !self.body.is_empty() {
+            write!(f, ": {}", self.body)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Error, Debug, Eq, PartialEq)]
 pub enum HTTPError {
     #[error("HTTP Error Code '{0}'")]
     Other(StatusCode),
     #[error("The requested value is not found")]
     NotFound,
+    /// 401. The token is missing, expired or revoked
+    #[error("Unauthorized {0}")]
+    Unauthorized(Box<ErrorResponse>),
+    /// 403. The user is not allowed to do that
+    #[error("Forbidden {0}")]
+    Forbidden(Box<ErrorResponse>),
+    /// 429. The rate limit was used up
+    #[error("Too Many Requests {0}")]
+    TooManyRequests(Box<ErrorResponse>),
+    /// 5xx. Reddit is having trouble
+    #[error("Server Error {0}")]
+    ServerError(Box<ErrorResponse>),
+}
+
+impl HTTPError {
+    /// The response details. None for [HTTPError::Other] and [HTTPError::NotFound]
+    pub fn response(&self) -> Option<&ErrorResponse> {
+        match self {
+            HTTPError::Unauthorized(response)
+            | HTTPError::Forbidden(response)
+            | HTTPError::TooManyRequests(response)
+            | HTTPError::ServerError(response) => Some(response),
+            HTTPError::Other(_) | HTTPError::NotFound => None,
+        }
+    }
+    /// The status code Reddit responded with
+    pub fn status(&self) -> StatusCode {
+        match self {
+            HTTPError::Other(status) => *status,
+            HTTPError::NotFound => StatusCode::NOT_FOUND,
+            HTTPError::Unauthorized(response)
+            | HTTPError::Forbidden(response)
+            | HTTPError::TooManyRequests(response)
+            | HTTPError::ServerError(response) => response.status,
+        }
+    }
 }
 
 impl From<StatusCode> for HTTPError {
@@ -31,3 +98,54 @@ impl From<StatusCode> for HTTPError {
         }
     }
 }
+
+/// Returns the response if it was successful. Otherwise reads the body into an [HTTPError]
+pub(crate) async fn check_status(
+    method: Method,
+    response: Response,
+) -> Result<Response, HTTPError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let wrap = match status {
+        StatusCode::UNAUTHORIZED => HTTPError::Unauthorized,
+        StatusCode::FORBIDDEN => HTTPError::Forbidden,
+        StatusCode::TOO_MANY_REQUESTS => HTTPError::TooManyRequests,
+        status if status.is_server_error() => HTTPError::ServerError,
+        status => return Err(HTTPError::from(status)),
+    };
+    let path = response.url().path().to_string();
+    let mut headers = HeaderMap::new();
+    for name in [
+        RETRY_AFTER,
+        WWW_AUTHENTICATE,
+        HeaderName::from_static(RATE_LIMIT_REMAINING),
+        HeaderName::from_static(RATE_LIMIT_USED),
+        HeaderName::from_static(RATE_LIMIT_RESET),
+    ] {
+        if let Some(value) = response.headers().get(&name) {
+            headers.insert(name, value.clone());
+        }
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(wrap(Box::new(ErrorResponse {
+        status,
+        method,
+        path,
+        body: truncate(body),
+        headers,
+    })))
+}
+
+fn truncate(mut body: String) -> String {
+    if body.len() > MAX_BODY_LENGTH {
+        let mut end = MAX_BODY_LENGTH;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+        body.push_str("...");
+    }
+    body
+}
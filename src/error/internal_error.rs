@@ -6,6 +6,8 @@ pub enum InternalError {
     ReqwestError(reqwest::Error),
     #[error("Serde Json Parse Error {0}")]
     JSONError(serde_json::Error),
+    #[error("IO Error {0}")]
+    IOError(std::io::Error),
     #[error("Internal Error {0}")]
     Custom(String),
 }
@@ -2,6 +2,7 @@ pub mod http_error;
 pub mod internal_error;
 pub mod reddit_error;
 
+use crate::auth::Scope;
 use crate::error::http_error::HTTPError;
 use crate::error::internal_error::InternalError;
 use crate::error::reddit_error::RedditError;
@@ -22,6 +23,48 @@ pub enum Error {
     Other(String),
     #[error("The Token Has Expired")]
     TokenExpired,
+    #[error("No refresh token is available to refresh the token")]
+    NoRefreshToken,
+    #[error("The token was not granted the {0} scope")]
+    MissingScope(Scope),
+    #[error("The state of the authorization redirect did not match")]
+    StateMismatch,
+    #[error("Reddit did not authorize the app: {0}")]
+    AuthorizationFailed(String),
+}
+
+impl Error {
+    /// If sending the request again later may succeed.
+    /// Rate limits, server errors, timeouts and connection failures
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::HTTPError(HTTPError::TooManyRequests(_) | HTTPError::ServerError(_)) => true,
+            Error::HTTPError(HTTPError::Other(status)) => {
+                *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            Error::RedditError(RedditError::RateLimited { .. }) => true,
+            Error::InternalError(InternalError::ReqwestError(error)) => {
+                error.is_timeout() || error.is_connect()
+            }
+            _ => false,
+        }
+    }
+    /// If the token is missing, expired or revoked
+    pub fn is_auth_failure(&self) -> bool {
+        match self {
+            Error::HTTPError(HTTPError::Unauthorized(_)) | Error::TokenExpired => true,
+            Error::HTTPError(HTTPError::Other(status)) => *status == StatusCode::UNAUTHORIZED,
+            _ => false,
+        }
+    }
+    /// If the user is not allowed to do that
+    pub fn is_forbidden(&self) -> bool {
+        match self {
+            Error::HTTPError(HTTPError::Forbidden(_)) => true,
+            Error::HTTPError(HTTPError::Other(status)) => *status == StatusCode::FORBIDDEN,
+            _ => false,
+        }
+    }
 }
 
 impl From<RedditError> for Error {
@@ -1,7 +1,65 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
 #[derive(Error, Debug, Eq, PartialEq)]
 pub enum RedditError {
     #[error("The Data Type specified in not valid {0}")]
     InvalidDataType(String),
+    /// `RATELIMIT`. Wait `retry_after` before trying again
+    #[error("Rate Limited by Reddit: {message}")]
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// `USER_REQUIRED`. The endpoint requires a logged in user
+    #[error("A User is required")]
+    UserRequired,
+    /// `SUBREDDIT_NOEXIST`
+    #[error("The Subreddit does not exist")]
+    SubredditNotExist,
+    /// `NO_TEXT`. A required field was empty
+    #[error("Missing text for {field:?}")]
+    NoText { field: Option<String> },
+    /// `TOO_LONG`. A field is longer than Reddit allows
+    #[error("Text too long for {field:?}: {message}")]
+    TooLong {
+        message: String,
+        field: Option<String>,
+    },
+    /// Any other error Reddit responded with. Code, message and field
+    #[error("Reddit responded with {0}: {1}")]
+    Unknown(String, String, Option<String>),
+}
+
+impl RedditError {
+    /// Creates the error from the `[code, message, field]` Reddit responded with
+    pub fn from_api(code: String, message: String, field: Option<String>) -> RedditError {
+        match code.as_str() {
+            "RATELIMIT" => RedditError::RateLimited {
+                retry_after: parse_retry_after(&message),
+                message,
+            },
+            "USER_REQUIRED" => RedditError::UserRequired,
+            "SUBREDDIT_NOEXIST" => RedditError::SubredditNotExist,
+            "NO_TEXT" => RedditError::NoText { field },
+            "TOO_LONG" => RedditError::TooLong { message, field },
+            _ => RedditError::Unknown(code, message, field),
+        }
+    }
+}
+
+/// Parses the wait out of messages like `you are doing that too much. try again in 5 minutes.`
+fn parse_retry_after(message: &str) -> Option<Duration> {
+    let (_, wait) = message.split_once("try again in ")?;
+    let mut words = wait.split_whitespace();
+    let amount: u64 = words.next()?.parse().ok()?;
+    let unit = words.next()?.trim_end_matches('.');
+    match unit {
+        "millisecond" | "milliseconds" => Some(Duration::from_millis(amount)),
+        "second" | "seconds" => Some(Duration::from_secs(amount)),
+        "minute" | "minutes" => Some(Duration::from_secs(amount * 60)),
+        "hour" | "hours" => Some(Duration::from_secs(amount * 60 * 60)),
+        _ => None,
+    }
 }
@@ -1,58 +1,86 @@
+pub mod actions;
 pub mod auth;
+pub mod builder;
 pub mod comments;
 pub mod error;
 pub mod message;
+pub mod rate_limit;
 pub mod responses;
+pub mod retry;
+pub mod stream;
 pub mod submission;
 pub mod subreddit;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod user;
 pub mod utils;
 
-use log::trace;
-use std::fmt::Write;
+use log::{debug, trace, warn};
 use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use reqwest::header::HeaderMap;
-use reqwest::{Body, Client as ReqwestClient, ClientBuilder, Response};
+use futures_util::Stream;
+use reqwest::header::{HeaderMap, AUTHORIZATION, WWW_AUTHENTICATE};
+use reqwest::{Body, Client as ReqwestClient, Method, Request, Response};
 use serde::de::DeserializeOwned;
-use submission::response::SubmissionsResponse;
+use serde::Deserialize;
+use serde_json::Value;
+use submission::response::{SubmissionResponse, SubmissionsResponse};
 
-use crate::auth::{Authenticator, Authorized};
-use crate::error::http_error::IntoResult;
+use crate::auth::{Authenticator, Authorized, Scope};
+use crate::builder::{ClientBuilder, Endpoints};
+use crate::comments::response::{CommentResponse, CommentsResponse};
+use crate::error::http_error::check_status;
 use crate::error::internal_error::InternalError;
 use crate::error::Error;
+use crate::rate_limit::RateLimit;
+use crate::responses::api::APIResponse;
+use crate::responses::GenericResponse;
+use crate::retry::RetryPolicy;
+use crate::stream::{poll_new, StreamOptions};
 use crate::subreddit::response::{SubredditResponse, Subreddits};
 use crate::subreddit::Subreddit;
 use crate::user::me::Me;
 use crate::user::response::{MeResponse, UserResponse, Users};
 use crate::user::User;
 use crate::utils::options::FeedOption;
+use crate::utils::request::ApiRequest;
 
 macro_rules! get_auth {
     ($se:ident) => {{
+        #[cfg(not(feature = "shared_authentication"))]
+        let needs_refresh = $se
+            .get_authenticator()
+            .needs_token_refresh($se.refresh_margin);
+        #[cfg(feature = "shared_authentication")]
+        let needs_refresh = $se
+            .get_authenticator()
+            .await
+            .needs_token_refresh($se.refresh_margin);
+        if needs_refresh {
+            $se.refresh_if_needed().await?;
+        }
         #[cfg(not(feature = "shared_authentication"))]
         let auth = $se.get_authenticator();
         #[cfg(feature = "shared_authentication")]
         let auth = $se.get_authenticator().await;
-        if auth.needs_token_refresh() {
-            trace!("Token Expired.");
-            #[cfg(not(feature = "shared_authentication"))]
-            {
-                return Err(Error::TokenExpired);
-            }
-            #[cfg(feature = "shared_authentication")]
-            {
-                trace!("Refreshing Token");
-                drop(auth);
-                $se.re_login().await?;
-                $se.get_authenticator().await
-            }
-        } else {
-            auth
-        }
+        auth
     }};
 }
 
+/// The Authenticator of a Client without `shared_authentication`. Clones of the Client get their own copy
+#[cfg(not(feature = "shared_authentication"))]
+#[derive(Debug)]
+struct LocalAuthenticator<A>(std::sync::RwLock<A>);
+
+#[cfg(not(feature = "shared_authentication"))]
+impl<A: Clone> Clone for LocalAuthenticator<A> {
+    fn clone(&self) -> Self {
+        LocalAuthenticator(std::sync::RwLock::new(self.0.read().unwrap().clone()))
+    }
+}
+
 /// This is who you are. This is your identity and you access point to the Reddit API
 #[derive(Clone)]
 pub struct Client<A: Authenticator> {
@@ -60,90 +88,78 @@ pub struct Client<A: Authenticator> {
     #[cfg(not(target_arch = "wasm32"))]
     auth: std::sync::Arc<tokio::sync::RwLock<A>>,
     #[cfg(not(feature = "shared_authentication"))]
-    auth: A,
+    auth: LocalAuthenticator<A>,
     client: ReqwestClient,
     user_agent: String,
+    endpoints: Endpoints,
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    wait_on_rate_limit: bool,
+    retry_policy: RetryPolicy,
+    lenient: bool,
     refresh_token: Option<String>,
+    refresh_margin: Duration,
+    replay_non_idempotent: bool,
     pub oauth: bool,
 }
 
 impl<A: Authenticator> Client<A> {
     /// Creates a Instance of the Client. Complete Initial Login Steps
+    ///
+    /// Use [ClientBuilder] to change the Reddit endpoints
     #[cfg(not(target_arch = "wasm32"))]
-    #[cfg(feature = "shared_authentication")]
-    pub async fn login<S: Into<String>>(mut auth: A, user_agent: S) -> Result<Client<A>, Error> {
-        let user_agent = user_agent.into();
-        let client = ClientBuilder::new()
-            .user_agent(user_agent.clone())
-            .build()?;
-        let b = auth.oauth();
-        let _x = auth.login(&client, &user_agent).await?;
-        let r_t = auth.get_refresh_token();
-        Ok(Client {
-            auth: std::sync::Arc::new(tokio::sync::RwLock::new(auth)),
-            client,
-            user_agent,
-            oauth: b,
-            refresh_token: r_t,
-        })
-    }
-    #[cfg(not(target_arch = "wasm32"))]
-    #[cfg(not(feature = "shared_authentication"))]
-    pub async fn login<S: Into<String>>(mut auth: A, user_agent: S) -> Result<Client<A>, Error> {
-        let user_agent = user_agent.into();
-        let client = ClientBuilder::new()
-            .user_agent(user_agent.clone())
-            .build()?;
-        let b = auth.oauth();
-        let r_t = auth.get_refresh_token();
-        auth.login(&client, &user_agent).await?;
-        Ok(Client {
-            auth,
-            client,
-            user_agent,
-            oauth: b,
-            refresh_token: r_t,
-        })
+    pub async fn login<S: Into<String>>(auth: A, user_agent: S) -> Result<Client<A>, Error> {
+        ClientBuilder::new(user_agent).login(auth).await
     }
 
     /// Loads a Domain listing
     /// ```rust
+    /// # #[cfg(feature = "testing")]
     /// #[tokio::main]
     /// async fn main() ->anyhow::Result<()>{
     ///    use log::LevelFilter;
-    /// use rraw::auth::AnonymousAuthenticator;
-    ///    use rraw::Client;
+    ///    use rraw::auth::AnonymousAuthenticator;
+    ///    use rraw::builder::ClientBuilder;
     ///    env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
-    ///    let client = Client:: login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
-    ///    let subreddit = client.domain("rust-lang.org",None);
+    /// #  let reddit = rraw::testing::MockReddit::start()?;
+    ///    let client = ClientBuilder::new("RRAW Test (by u/KingTuxWH)")
+    /// #      .endpoints(reddit.endpoints())
+    ///        .login(AnonymousAuthenticator::new())
+    ///        .await?;
+    ///    let submissions = client.domain("rust-lang.org", None).await?;
     ///    Ok(())
     /// }
+    /// # #[cfg(not(feature = "testing"))]
+    /// # fn main() {}
     /// ```
     pub async fn domain<T: Into<String>>(
         &self,
         name: T,
         feed_options: Option<FeedOption>,
     ) -> Result<SubmissionsResponse, Error> {
-        let mut path = format!("/domain/{}.json", name.into());
-        if let Some(options) = feed_options {
-            options.extend(&mut path)
-        }
+        let request = ApiRequest::new(format!("/domain/{}.json", name.into())).feed(feed_options);
         return self
-            .get_json::<SubmissionsResponse>(&path, false, true)
+            .get_json::<SubmissionsResponse>(&request.url(), false, true)
             .await;
     }
     /// Loads SubReddit
     /// ```rust
+    /// # #[cfg(feature = "testing")]
     /// #[tokio::main]
     /// async fn main() ->anyhow::Result<()>{
     ///    use log::LevelFilter;
-    /// use rraw::auth::AnonymousAuthenticator;
-    ///    use rraw::Client;
+    ///    use rraw::auth::AnonymousAuthenticator;
+    ///    use rraw::builder::ClientBuilder;
     ///    env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
-    ///    let client = Client:: login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
-    ///    let subreddit = client.subreddit("rust");
+    /// #  let reddit = rraw::testing::MockReddit::start()?;
+    ///    let client = ClientBuilder::new("RRAW Test (by u/KingTuxWH)")
+    /// #      .endpoints(reddit.endpoints())
+    ///        .login(AnonymousAuthenticator::new())
+    ///        .await?;
+    ///    let subreddit = client.subreddit("rust").await?;
     ///    Ok(())
     /// }
+    /// # #[cfg(not(feature = "testing"))]
+    /// # fn main() {}
     /// ```
     pub async fn subreddit<T: Into<String>>(&self, name: T) -> Result<Subreddit<'_, A>, Error> {
         let string = format!("/r/{}/about.json", name.into());
@@ -158,16 +174,23 @@ impl<A: Authenticator> Client<A> {
 
     /// Creates a User struct.
     /// ```rust
+    /// # #[cfg(feature = "testing")]
     /// #[tokio::main]
     /// async fn main() ->anyhow::Result<()>{
     ///    use log::LevelFilter;
-    /// use rraw::auth::AnonymousAuthenticator;
-    ///    use rraw::Client;
+    ///    use rraw::auth::AnonymousAuthenticator;
+    ///    use rraw::builder::ClientBuilder;
     ///    env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
-    ///    let client = Client:: login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
-    ///    let subreddit = client.user("KingTuxWH").await?;
+    /// #  let reddit = rraw::testing::MockReddit::start()?;
+    ///    let client = ClientBuilder::new("RRAW Test (by u/KingTuxWH)")
+    /// #      .endpoints(reddit.endpoints())
+    ///        .login(AnonymousAuthenticator::new())
+    ///        .await?;
+    ///    let user = client.user("KingTuxWH").await?;
     ///    Ok(())
     /// }
+    /// # #[cfg(not(feature = "testing"))]
+    /// # fn main() {}
     /// ```
     pub async fn user<T: Into<String>>(&self, name: T) -> Result<User<'_, A>, Error> {
         let string = format!("/u/{}/about", name.into());
@@ -180,16 +203,23 @@ impl<A: Authenticator> Client<A> {
 
     /// Searches for Subreddits by name
     /// ```rust
+    /// # #[cfg(feature = "testing")]
     /// #[tokio::main]
     /// async fn main() ->anyhow::Result<()>{
     ///    use log::LevelFilter;
-    /// use rraw::auth::AnonymousAuthenticator;
-    ///    use rraw::Client;
+    ///    use rraw::auth::AnonymousAuthenticator;
+    ///    use rraw::builder::ClientBuilder;
     ///    env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
-    ///    let client = Client:: login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
+    /// #  let reddit = rraw::testing::MockReddit::start()?;
+    ///    let client = ClientBuilder::new("RRAW Test (by u/KingTuxWH)")
+    /// #      .endpoints(reddit.endpoints())
+    ///        .login(AnonymousAuthenticator::new())
+    ///        .await?;
     ///    let subreddits = client.search_subreddits("rust", None, None).await?;
     ///    Ok(())
     /// }
+    /// # #[cfg(not(feature = "testing"))]
+    /// # fn main() {}
     /// ```
     pub async fn search_subreddits<S: Into<String>>(
         &self,
@@ -197,28 +227,33 @@ impl<A: Authenticator> Client<A> {
         limit: Option<u64>,
         feed: Option<FeedOption>,
     ) -> crate::error::Result<Subreddits> {
-        let mut url = format!("/subreddits/search?q={}", name.into());
-        if let Some(options) = feed {
-            url.push_str(options.url().as_str());
-        }
-        if let Some(limit) = limit {
-            let _ = write!(url, "&limit={}", limit);
-        }
-        self.get_json::<Subreddits>(&url, false, false).await
+        let request = ApiRequest::new("/subreddits/search")
+            .query("q", name.into())
+            .feed(feed)
+            .query_opt("limit", limit);
+        self.get_json::<Subreddits>(&request.url(), false, false)
+            .await
     }
 
     /// Searches for Subreddits by name
     /// ```rust
+    /// # #[cfg(feature = "testing")]
     /// #[tokio::main]
     /// async fn main() ->anyhow::Result<()>{
     ///    use log::LevelFilter;
-    /// use rraw::auth::AnonymousAuthenticator;
-    ///    use rraw::Client;
+    ///    use rraw::auth::AnonymousAuthenticator;
+    ///    use rraw::builder::ClientBuilder;
     ///    env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
-    ///    let client = Client:: login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
-    ///    let users = client.search_users("King", None, None).await?;    
+    /// #  let reddit = rraw::testing::MockReddit::start()?;
+    ///    let client = ClientBuilder::new("RRAW Test (by u/KingTuxWH)")
+    /// #      .endpoints(reddit.endpoints())
+    ///        .login(AnonymousAuthenticator::new())
+    ///        .await?;
+    ///    let users = client.search_users("King", None, None).await?;
     ///    Ok(())
     /// }
+    /// # #[cfg(not(feature = "testing"))]
+    /// # fn main() {}
     /// ```
     pub async fn search_users<S: Into<String>>(
         &self,
@@ -226,25 +261,101 @@ impl<A: Authenticator> Client<A> {
         limit: Option<u64>,
         feed: Option<FeedOption>,
     ) -> crate::error::Result<Users> {
-        let mut url = format!("/users/search?raw_json=1&q={}", name.into());
-        if let Some(options) = feed {
-            url.push_str(options.url().as_str());
-        }
-        if let Some(limit) = limit {
-            let _ = write!(url, "&limit={}", limit);
-        }
-        self.get_json::<Users>(&url, false, false).await
+        let request = ApiRequest::new("/users/search")
+            .query("raw_json", 1)
+            .query("q", name.into())
+            .feed(feed)
+            .query_opt("limit", limit);
+        self.get_json::<Users>(&request.url(), false, false).await
     }
+    /// Streams new submissions as they are posted.
+    ///
+    /// `subreddit` can be a combination such as `rust+programming` or `all`. See [poll_new]
+    /// ```no_run
+    /// #[tokio::main]
+    /// async fn main() ->anyhow::Result<()>{
+    ///    use futures_util::StreamExt;
+    /// use rraw::auth::AnonymousAuthenticator;
+    ///    use rraw::Client;
+    ///    let client = Client:: login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
+    ///    let mut submissions = Box::pin(client.stream_submissions("rust+programming", None));
+    ///    while let Some(submission) = submissions.next().await {
+    ///        println!("{:?}", submission?);
+    ///    }
+    ///    Ok(())
+    /// }
+    /// ```
+    pub fn stream_submissions<S: Into<String>>(
+        &self,
+        subreddit: S,
+        options: Option<StreamOptions>,
+    ) -> impl Stream<Item = Result<GenericResponse<SubmissionResponse>, Error>> + '_ {
+        let path = format!("/r/{}/new", subreddit.into());
+        poll_new(options.unwrap_or_default(), move |feed| {
+            let path = ApiRequest::new(path.clone()).feed(Some(feed)).url();
+            async move {
+                self.get_json::<SubmissionsResponse>(&path, false, false)
+                    .await
+            }
+        })
+    }
+    /// Streams new comments as they are posted.
+    ///
+    /// `subreddit` can be a combination such as `rust+programming` or `all`. See [poll_new]
+    pub fn stream_comments<S: Into<String>>(
+        &self,
+        subreddit: S,
+        options: Option<StreamOptions>,
+    ) -> impl Stream<Item = Result<GenericResponse<CommentResponse>, Error>> + '_ {
+        let path = format!("/r/{}/comments", subreddit.into());
+        poll_new(options.unwrap_or_default(), move |feed| {
+            let path = ApiRequest::new(path.clone()).feed(Some(feed)).url();
+            async move { self.get_json::<CommentsResponse>(&path, false, false).await }
+        })
+    }
+    /// Refreshes the token. The current token stays in use until the refresh succeeded
     #[cfg(not(feature = "shared_authentication"))]
-    pub async fn re_login(&mut self) -> Result<bool, error::Error> {
-        self.auth
-            .token_refresh(&self.client, &self.user_agent)
-            .await
+    pub async fn re_login(&self) -> Result<bool, error::Error> {
+        let mut auth = self.get_authenticator().clone();
+        let refreshed = auth
+            .token_refresh(&self.client, &self.user_agent, &self.endpoints)
+            .await?;
+        *self.auth.0.write().unwrap() = auth;
+        Ok(refreshed)
     }
+    /// Refreshes the token
     #[cfg(feature = "shared_authentication")]
     pub async fn re_login(&self) -> Result<bool, error::Error> {
         let mut guard = self.auth.write().await;
-        guard.token_refresh(&self.client, &self.user_agent).await
+        guard
+            .token_refresh(&self.client, &self.user_agent, &self.endpoints)
+            .await
+    }
+    /// Refreshes the token unless another request refreshed it while this one waited for the lock.
+    /// Concurrent requests with an expired token send a single refresh
+    async fn refresh_if_needed(&self) -> Result<(), error::Error> {
+        self.refresh_if(|auth| auth.needs_token_refresh(self.refresh_margin))
+            .await
+    }
+    /// Refreshes the token if `stale` is still true once the write lock is held
+    #[cfg(feature = "shared_authentication")]
+    async fn refresh_if(&self, stale: impl Fn(&A) -> bool) -> Result<(), error::Error> {
+        let mut guard = self.auth.write().await;
+        if stale(&guard) {
+            trace!("Refreshing Token");
+            guard
+                .token_refresh(&self.client, &self.user_agent, &self.endpoints)
+                .await?;
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "shared_authentication"))]
+    async fn refresh_if(&self, stale: impl Fn(&A) -> bool) -> Result<(), error::Error> {
+        if stale(&self.get_authenticator()) {
+            trace!("Refreshing Token");
+            self.re_login().await?;
+        }
+        Ok(())
     }
 }
 
@@ -255,8 +366,143 @@ impl<A: Authenticator> Client<A> {
         self.auth.read().await
     }
     #[cfg(not(feature = "shared_authentication"))]
-    pub(crate) fn get_authenticator(&self) -> &A {
-        &self.auth
+    pub(crate) fn get_authenticator(&self) -> std::sync::RwLockReadGuard<'_, A> {
+        self.auth.0.read().unwrap()
+    }
+    /// The Reddit endpoints this Client uses
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+    /// The Rate Limit reported by the last response. None if Reddit has not sent one yet
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
+    }
+    /// Sends the request. Retrying transient failures according to the RetryPolicy
+    async fn execute(&self, mut request: Request) -> Result<Response, Error> {
+        let policy = &self.retry_policy;
+        let retry = request.method().is_idempotent() || policy.retry_non_idempotent;
+        let mut attempt = 1;
+        loop {
+            let next = if retry && attempt < policy.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+            let method = request.method().clone();
+            let url = request.url().clone();
+            let response = self.send(request).await;
+            let next = match next {
+                Some(next) => next,
+                None => return response,
+            };
+            let (delay, reason) = match &response {
+                Ok(response) if RetryPolicy::is_retryable_status(response.status()) => (
+                    policy.delay(attempt, response.headers()),
+                    response.status().to_string(),
+                ),
+                Err(Error::InternalError(InternalError::ReqwestError(error)))
+                    if RetryPolicy::is_retryable_error(error) =>
+                {
+                    (policy.backoff(attempt), error.to_string())
+                }
+                _ => return response,
+            };
+            warn!(
+                "{} {} failed with {}. Retrying in {:?} (Attempt {}/{})",
+                method,
+                url.path(),
+                reason,
+                delay,
+                attempt + 1,
+                policy.max_attempts
+            );
+            utils::sleep(delay).await;
+            request = next;
+            attempt += 1;
+        }
+    }
+    /// Sends the request. If Reddit rejects the token it is refreshed and the request is sent once more.
+    ///
+    /// Non-idempotent requests are only sent again if [ClientBuilder::replay_non_idempotent] is enabled
+    async fn execute_authorized(
+        &self,
+        request: Request,
+        headers: HeaderMap,
+    ) -> Result<Response, Error> {
+        let replay = if request.method().is_idempotent() || self.replay_non_idempotent {
+            request.try_clone()
+        } else {
+            None
+        };
+        let response = self.execute(request).await?;
+        let mut replay = match replay {
+            Some(replay) if is_invalid_token(&response) => replay,
+            _ => return Ok(response),
+        };
+        warn!(
+            "{} {} was rejected with an invalid token. Refreshing it and sending the request again",
+            replay.method(),
+            replay.url().path()
+        );
+        // Another request may have refreshed the token already
+        self.refresh_if(|auth| {
+            let mut current = HeaderMap::new();
+            auth.headers(&mut current);
+            current.get(AUTHORIZATION) == headers.get(AUTHORIZATION)
+        })
+        .await?;
+        let (_, headers) = self
+            .authorization(replay.method(), replay.url().path())
+            .await?;
+        replay.headers_mut().extend(headers);
+        self.execute(replay).await
+    }
+    /// Sends the request. Waiting for the Rate Limit to reset first if configured
+    async fn send(&self, request: Request) -> Result<Response, Error> {
+        if self.wait_on_rate_limit {
+            let rate_limit = self.rate_limit();
+            if let Some(rate_limit) = rate_limit.filter(RateLimit::is_exhausted) {
+                let wait = rate_limit.reset_in();
+                debug!("Rate Limit used up. Waiting {:?}", wait);
+                utils::sleep(wait).await;
+            }
+        }
+        let response = self.client.execute(request).await?;
+        if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+            trace!("Rate Limit: {:?}", rate_limit);
+            *self.rate_limit.lock().unwrap() = Some(rate_limit);
+        }
+        Ok(response)
+    }
+    /// The scopes Reddit granted the current token. None if unknown
+    pub async fn scopes(&self) -> Option<Vec<Scope>> {
+        #[cfg(feature = "shared_authentication")]
+        let authenticator = self.get_authenticator().await;
+        #[cfg(not(feature = "shared_authentication"))]
+        let authenticator = self.get_authenticator();
+        authenticator.scopes()
+    }
+    /// True if Reddit granted the scope to the current token
+    pub async fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes()
+            .await
+            .map(|scopes| scope.granted_by(&scopes))
+            .unwrap_or(false)
+    }
+    /// Refreshes the token if needed. Fails before sending if the endpoint requires a scope that was not granted.
+    /// Returns if the Authenticator uses OAuth and its headers
+    async fn authorization(&self, method: &Method, url: &str) -> Result<(bool, HeaderMap), Error> {
+        let authenticator = get_auth!(self);
+        if let (Some(scopes), Some(required)) =
+            (authenticator.scopes(), Scope::required_for(method, url))
+        {
+            if !required.granted_by(&scopes) {
+                return Err(Error::MissingScope(required));
+            }
+        }
+        let mut headers = HeaderMap::new();
+        authenticator.headers(&mut headers);
+        Ok((authenticator.oauth(), headers))
     }
     pub(crate) async fn get(
         &self,
@@ -264,35 +510,22 @@ impl<A: Authenticator> Client<A> {
         oauth: bool,
         private_api: bool,
     ) -> Result<Response, Error> {
-        let authenticator = get_auth!(self);
-        let string = self.build_url(url, oauth, authenticator.oauth(), private_api);
-        let mut headers = HeaderMap::new();
-        authenticator.headers(&mut headers);
-        #[cfg(feature = "shared_authentication")]
-        drop(authenticator);
-        self.client
-            .get(string)
-            .headers(headers)
-            .send()
-            .await
-            .map_err(|error| Error::InternalError(InternalError::ReqwestError(error)))
+        let (authenticator_oauth, headers) = self.authorization(&Method::GET, url).await?;
+        let string = self.build_url(url, oauth, authenticator_oauth, private_api);
+        let request = self.client.get(string).headers(headers.clone()).build()?;
+        self.execute_authorized(request, headers).await
     }
     /// Makes a post request with Reqwest response
     pub(crate) async fn post(&self, url: &str, oauth: bool, body: Body) -> Result<Response, Error> {
-        let authenticator = get_auth!(self);
-
-        let string = self.build_url(url, oauth, authenticator.oauth(), false);
-        let mut headers = HeaderMap::new();
-        authenticator.headers(&mut headers);
-        #[cfg(feature = "shared_authentication")]
-        drop(authenticator);
-        self.client
+        let (authenticator_oauth, headers) = self.authorization(&Method::POST, url).await?;
+        let string = self.build_url(url, oauth, authenticator_oauth, false);
+        let request = self
+            .client
             .post(string)
             .body(body)
-            .headers(headers)
-            .send()
-            .await
-            .map_err(Error::from)
+            .headers(headers.clone())
+            .build()?;
+        self.execute_authorized(request, headers).await
     }
     /// Makes a get request with JSON response
     pub(crate) async fn get_json<T: DeserializeOwned>(
@@ -302,11 +535,8 @@ impl<A: Authenticator> Client<A> {
         private_api: bool,
     ) -> crate::error::Result<T> {
         let response = self.get(url, oauth, private_api).await?;
-        response.status().into_result()?;
-        response
-            .json()
-            .await
-            .map_err(|error| Error::InternalError(InternalError::ReqwestError(error)))
+        let response = check_status(Method::GET, response).await?;
+        self.decode(&response.bytes().await?)
     }
     /// Makes a post request with JSON response
     pub(crate) async fn post_json<T: DeserializeOwned>(
@@ -316,11 +546,40 @@ impl<A: Authenticator> Client<A> {
         body: Body,
     ) -> crate::error::Result<T> {
         let response = self.post(url, oauth, body).await?;
-        response.status().into_result()?;
-        response
-            .json()
-            .await
-            .map_err(|error| Error::InternalError(InternalError::ReqwestError(error)))
+        let response = check_status(Method::POST, response).await?;
+        self.decode(&response.bytes().await?)
+    }
+    /// Decodes a JSON body. Respecting [ClientBuilder::lenient]
+    fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> crate::error::Result<T> {
+        if self.lenient {
+            Ok(serde_json::from_slice::<responses::Lenient<T>>(body)?.0)
+        } else {
+            Ok(serde_json::from_slice(body)?)
+        }
+    }
+    /// Makes a post request to an `api_type=json` endpoint.
+    /// Errors in the envelope are returned as typed [RedditError](error::reddit_error::RedditError)s
+    pub(crate) async fn post_api<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: Body,
+    ) -> crate::error::Result<Option<T>> {
+        self.post_json::<APIResponse<T>>(url, true, body)
+            .await?
+            .into_result()
+    }
+    /// Makes a post request to an `api_type=json` endpoint. Returns the whole response body.
+    /// Errors in the envelope are returned as typed [RedditError](error::reddit_error::RedditError)s
+    pub(crate) async fn post_api_raw(&self, url: &str, body: Body) -> crate::error::Result<Value> {
+        let value = self.post_json::<Value>(url, true, body).await?;
+        APIResponse::<Value>::deserialize(&value)?.into_result()?;
+        Ok(value)
+    }
+    /// Makes a post request ignoring the response body
+    pub(crate) async fn post_empty(&self, url: &str, oauth: bool, body: Body) -> Result<(), Error> {
+        let response = self.post(url, oauth, body).await?;
+        check_status(Method::POST, response).await?;
+        Ok(())
     }
     pub(crate) fn build_url(
         &self,
@@ -330,7 +589,7 @@ impl<A: Authenticator> Client<A> {
         private_api: bool,
     ) -> String {
         let stem = if private_api {
-            "https://reddit.com"
+            &self.endpoints.reddit
         } else if oauth_required || oauth_supported {
             // All endpoints support OAuth, but some do not support the regular endpoint. If we are
             // required to use it or support it, we will use it.
@@ -339,9 +598,9 @@ impl<A: Authenticator> Client<A> {
                 "OAuth is required to use this endpoint, but your authenticator does not \
                      support it."
             );
-            "https://oauth.reddit.com"
+            &self.endpoints.oauth
         } else {
-            "https://api.reddit.com"
+            &self.endpoints.api
         };
         format!("{stem}{dest}")
     }
@@ -349,18 +608,24 @@ impl<A: Authenticator> Client<A> {
 
 impl<A: Authorized> Client<A> {
     /// Gets the User Inbox Struct
-    /// ```no_run
+    /// ```rust
+    /// # #[cfg(feature = "testing")]
     /// #[tokio::main]
     /// async fn main() ->anyhow::Result<()>{
-    ///    use std::env;
-    /// use log::LevelFilter;
-    ///    use rraw::auth::{ PasswordAuthenticator};
-    ///    use rraw::Client;
-    ///   env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
-    ///    let client = Client:: login(PasswordAuthenticator::new(env::var("CLIENT_ID")?,env::var("CLIENT_SECRET")?,env::var("USERNAME")?,env::var("PASSWORD")?), "RRAW Test (by u/KingTuxWH)").await?;
-    ///    let me = client.me().await?;    
+    ///    use log::LevelFilter;
+    ///    use rraw::auth::PasswordAuthenticator;
+    ///    use rraw::builder::ClientBuilder;
+    ///    env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
+    /// #  let reddit = rraw::testing::MockReddit::start()?;
+    ///    let client = ClientBuilder::new("RRAW Test (by u/KingTuxWH)")
+    /// #      .endpoints(reddit.endpoints())
+    ///        .login(PasswordAuthenticator::new("CLIENT_ID", "CLIENT_SECRET", "USERNAME", "PASSWORD"))
+    ///        .await?;
+    ///    let me = client.me().await?;
     ///    Ok(())
     /// }
+    /// # #[cfg(not(feature = "testing"))]
+    /// # fn main() {}
     /// ```
     pub async fn me(&self) -> Result<Me<'_, A>, Error> {
         let me: MeResponse = self.get_json("/api/v1/me", true, false).await?;
@@ -370,15 +635,19 @@ impl<A: Authorized> Client<A> {
     ///
     /// Note: Refresh Token only will be exist when using CodeAuthenticator with an Permanent Duration Authorization Code.
     /// The Refresh Token must be stored in a secure manner such as using the platform's Secret/Keyring service for future use.
-    /// ```no_run
+    /// ```rust
+    /// # #[cfg(feature = "testing")]
     /// #[tokio::main]
     /// async fn main() ->anyhow::Result<()>{
-    ///    use std::env;
-    /// use log::LevelFilter;
-    ///    use rraw::auth::{ CodeAuthenticator};
-    ///    use rraw::Client;
-    ///   env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
-    ///    let client = Client:: login(CodeAuthenticator::new(env::var("CLIENT_ID")?,env::var("CLIENT_SECRET")?,env::var("CODE")?,env::var("REDIRECT_URI")?), "RRAW Test (by u/KingTuxWH)").await?;
+    ///    use log::LevelFilter;
+    ///    use rraw::auth::CodeAuthenticator;
+    ///    use rraw::builder::ClientBuilder;
+    ///    env_logger::builder().is_test(true).filter_level(LevelFilter::Trace).try_init();
+    /// #  let reddit = rraw::testing::MockReddit::start()?;
+    ///    let client = ClientBuilder::new("RRAW Test (by u/KingTuxWH)")
+    /// #      .endpoints(reddit.endpoints())
+    ///        .login(CodeAuthenticator::new("CLIENT_ID", "CLIENT_SECRET", "CODE", "REDIRECT_URI"))
+    ///        .await?;
     ///    let refresh_token = client.refresh_token();
     ///    if refresh_token.is_some() {
     ///        println!("Refresh Token Is: {}", refresh_token.unwrap());
@@ -387,6 +656,8 @@ impl<A: Authorized> Client<A> {
     ///    }
     ///    Ok(())
     /// }
+    /// # #[cfg(not(feature = "testing"))]
+    /// # fn main() {}
     /// ```
     pub fn refresh_token(&self) -> Option<String> {
         self.refresh_token.to_owned()
@@ -402,3 +673,14 @@ impl<A: Authenticator> Debug for Client<A> {
         )
     }
 }
+
+/// A 401 with `WWW-Authenticate: ... error="invalid_token"`. The token expired early or was revoked
+fn is_invalid_token(response: &Response) -> bool {
+    response.status() == reqwest::StatusCode::UNAUTHORIZED
+        && response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.contains("invalid_token"))
+            .unwrap_or(false)
+}
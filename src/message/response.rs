@@ -1,7 +1,8 @@
 use crate::responses::listing::GenericListing;
-use crate::responses::{FullName, GenericResponse};
+use crate::responses::{FullName, GenericResponse, HasFullName, RedditDataType};
 use serde::Deserialize;
 use serde_json::Value;
+use std::str::FromStr;
 
 #[derive(Deserialize, Debug)]
 pub struct Message {
@@ -33,6 +34,15 @@ pub struct Message {
     #[serde(default)]
     pub was_comment: bool,
 }
+impl HasFullName for Message {
+    /// Comment replies in the inbox are `t1` not `t4`. So the name is preferred
+    fn full_name(&self) -> FullName {
+        FullName::from_str(&self.name).unwrap_or_else(|_| FullName {
+            reddit_type: RedditDataType::Message,
+            id: self.id.clone(),
+        })
+    }
+}
 /// About with a GenericResponse Wrap
 pub type MessageResponse = GenericResponse<Message>;
 /// A listing of user abouts
@@ -0,0 +1,48 @@
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+
+/// Header: Approximate number of requests left to use
+pub static RATE_LIMIT_REMAINING: &str = "x-ratelimit-remaining";
+/// Header: Approximate number of requests used in this period
+pub static RATE_LIMIT_USED: &str = "x-ratelimit-used";
+/// Header: Approximate number of seconds to end of period
+pub static RATE_LIMIT_RESET: &str = "x-ratelimit-reset";
+
+/// The Rate Limit budget Reddit reported on the last response
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    /// Requests left in this period
+    pub remaining: f64,
+    /// Requests used in this period
+    pub used: u64,
+    /// Seconds until the period resets. Relative to `received_at`
+    pub reset: u64,
+    /// When the headers were received
+    pub received_at: Instant,
+}
+
+impl RateLimit {
+    /// Parses the `X-Ratelimit-*` headers. None if any of them are missing
+    pub fn from_headers(headers: &HeaderMap) -> Option<RateLimit> {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        Some(RateLimit {
+            remaining: header(RATE_LIMIT_REMAINING)?.trim().parse().ok()?,
+            used: header(RATE_LIMIT_USED)?.trim().parse().ok()?,
+            reset: header(RATE_LIMIT_RESET)?.trim().parse().ok()?,
+            received_at: Instant::now(),
+        })
+    }
+    /// When the period resets
+    pub fn resets_at(&self) -> Instant {
+        self.received_at + Duration::from_secs(self.reset)
+    }
+    /// How long until the period resets. Zero if it already has
+    pub fn reset_in(&self) -> Duration {
+        self.resets_at().saturating_duration_since(Instant::now())
+    }
+    /// True if no requests are left and the period has not reset yet
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining < 1.0 && !self.reset_in().is_zero()
+    }
+}
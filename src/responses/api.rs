@@ -0,0 +1,97 @@
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::error::reddit_error::RedditError;
+use crate::error::Error;
+use crate::responses::RedditResponse;
+
+/// The envelope endpoints called with `api_type=json` respond with
+#[derive(Deserialize, Debug)]
+pub struct APIResponse<T> {
+    pub json: APIJson<T>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct APIJson<T> {
+    /// Errors Reddit reported. The request failed if this is not empty
+    #[serde(default)]
+    pub errors: Vec<APIError>,
+    /// The data. Some endpoints do not return any
+    pub data: Option<T>,
+    /// Seconds until the rate limit resets. Sent with `RATELIMIT` errors
+    pub ratelimit: Option<f64>,
+}
+
+impl<T> APIResponse<T> {
+    /// Returns the first error Reddit reported or the data
+    pub fn into_result(self) -> Result<Option<T>, Error> {
+        let error = match self.json.errors.into_iter().next() {
+            Some(error) => RedditError::from(error),
+            None => return Ok(self.json.data),
+        };
+        match (error, self.json.ratelimit) {
+            (RedditError::RateLimited { message, .. }, Some(seconds)) if seconds >= 0.0 => {
+                Err(Error::RedditError(RedditError::RateLimited {
+                    retry_after: Some(Duration::from_secs_f64(seconds)),
+                    message,
+                }))
+            }
+            (error, _) => Err(Error::RedditError(error)),
+        }
+    }
+}
+
+/// The data of endpoints that create or update things. Such as `/api/comment`
+#[derive(Deserialize, Debug)]
+pub struct Things {
+    pub things: Vec<RedditResponse>,
+}
+
+impl Things {
+    /// The first thing. Reddit only returns one for a reply or edit
+    pub fn into_first(self) -> Result<RedditResponse, Error> {
+        self.things
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::from("Reddit did not return the thing"))
+    }
+}
+
+/// A single error. Reddit sends it as `[code, message, field]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct APIError {
+    /// Example: `RATELIMIT`
+    pub code: String,
+    /// Human readable explanation
+    pub message: String,
+    /// The request field that caused the error
+    pub field: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for APIError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values: Vec<Value> = Vec::deserialize(deserializer)?;
+        let string = |index: usize| {
+            values
+                .get(index)
+                .and_then(Value::as_str)
+                .map(ToString::to_string)
+        };
+        Ok(APIError {
+            code: string(0).ok_or_else(|| DeError::custom("Missing Error Code"))?,
+            message: string(1).unwrap_or_default(),
+            field: string(2),
+        })
+    }
+}
+
+impl From<APIError> for RedditError {
+    fn from(error: APIError) -> Self {
+        RedditError::from_api(error.code, error.message, error.field)
+    }
+}
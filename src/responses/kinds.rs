@@ -0,0 +1,143 @@
+//! Data for the kinds that are not tied to a module of their own
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::responses::GenericResponse;
+use crate::user::response::AboutUser;
+
+/// A subreddit inside a [LabeledMulti]
+#[derive(Deserialize, Debug, Clone)]
+pub struct MultiSubreddit {
+    pub name: String,
+}
+
+/// Type: `LabeledMulti`. A custom feed
+#[derive(Deserialize, Debug, Clone)]
+pub struct LabeledMulti {
+    pub name: String,
+    pub display_name: String,
+    /// Example: `/user/spez/m/rust_and_friends/`
+    pub path: String,
+    pub owner: Option<String>,
+    pub description_md: Option<String>,
+    pub visibility: Option<String>,
+    #[serde(default)]
+    pub subreddits: Vec<MultiSubreddit>,
+    pub created_utc: Option<f64>,
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub over_18: bool,
+    #[serde(default)]
+    pub can_edit: bool,
+    pub copied_from: Option<String>,
+}
+
+/// Type: `modaction`. An entry in the moderation log
+#[derive(Deserialize, Debug, Clone)]
+pub struct ModAction {
+    /// Example: `ModAction_6a1b7b2e-...`
+    pub id: String,
+    /// Example: `removelink`
+    pub action: String,
+    /// The moderator
+    #[serde(rename = "mod")]
+    pub moderator: String,
+    pub mod_id36: Option<String>,
+    pub created_utc: f64,
+    pub subreddit: String,
+    pub sr_id36: Option<String>,
+    pub details: Option<String>,
+    pub description: Option<String>,
+    pub target_author: Option<String>,
+    pub target_fullname: Option<String>,
+    pub target_permalink: Option<String>,
+    pub target_title: Option<String>,
+    pub target_body: Option<String>,
+}
+
+/// A user inside a [UserList]
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserListEntry {
+    pub name: String,
+    /// The FullName of the user
+    pub id: String,
+    pub date: Option<f64>,
+    pub rel_id: Option<String>,
+    pub note: Option<String>,
+    /// Only for banned users
+    pub days_left: Option<u32>,
+    /// Only for moderators
+    pub mod_permissions: Option<Vec<String>>,
+}
+
+/// Type: `UserList`. Friends, banned users, contributors and such
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserList {
+    pub children: Vec<UserListEntry>,
+}
+
+/// Type: `t6`. A trophy. Reddit calls it an award
+#[derive(Deserialize, Debug, Clone)]
+pub struct Award {
+    pub name: String,
+    pub id: Option<String>,
+    pub award_id: Option<String>,
+    pub description: Option<String>,
+    pub icon_40: Option<String>,
+    pub icon_70: Option<String>,
+    pub url: Option<String>,
+    pub granted_at: Option<f64>,
+}
+
+/// Type: `TrophyList`
+#[derive(Deserialize, Debug)]
+pub struct TrophyList {
+    pub trophies: Vec<GenericResponse<Award>>,
+}
+
+/// Type: `wikipage`
+#[derive(Deserialize, Debug)]
+pub struct WikiPage {
+    pub content_md: String,
+    pub content_html: Option<String>,
+    #[serde(default)]
+    pub may_revise: bool,
+    pub reason: Option<String>,
+    pub revision_date: Option<f64>,
+    pub revision_id: Option<String>,
+    pub revision_by: Option<GenericResponse<AboutUser>>,
+}
+
+/// An image uploaded to a [Stylesheet]
+#[derive(Deserialize, Debug, Clone)]
+pub struct StylesheetImage {
+    pub name: String,
+    pub url: String,
+    /// Example: `url(%%name%%)`
+    pub link: Option<String>,
+}
+
+/// Type: `stylesheet`
+#[derive(Deserialize, Debug, Clone)]
+pub struct Stylesheet {
+    pub stylesheet: String,
+    #[serde(default)]
+    pub images: Vec<StylesheetImage>,
+    pub subreddit_id: Option<String>,
+}
+
+/// Type: `LiveUpdate`. An update in a live thread
+#[derive(Deserialize, Debug, Clone)]
+pub struct LiveUpdate {
+    pub id: String,
+    /// Example: `LiveUpdate_6a1b7b2e-...`
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub body: String,
+    pub body_html: Option<String>,
+    pub created_utc: f64,
+    #[serde(default)]
+    pub stricken: bool,
+    #[serde(default)]
+    pub embeds: Vec<Value>,
+}
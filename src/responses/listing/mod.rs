@@ -2,6 +2,8 @@ use crate::responses::{GenericResponse, RedditResponse};
 use serde::Deserialize;
 use std::fmt::{Debug, Formatter};
 
+pub mod paginator;
+
 #[derive(Deserialize)]
 /// The Listing API for async RRAW
 pub struct Listing<T> {
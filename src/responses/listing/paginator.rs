@@ -0,0 +1,87 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::future::Future;
+
+use futures_util::stream::{self, Stream};
+
+use crate::error::Error;
+use crate::responses::listing::GenericListing;
+use crate::responses::GenericResponse;
+use crate::utils::options::FeedOption;
+
+struct PageState<F, T: Debug> {
+    fetch: F,
+    /// None once the last page has been loaded
+    feed: Option<FeedOption>,
+    buffer: VecDeque<GenericResponse<T>>,
+    /// Items left before the cap is reached
+    remaining: Option<usize>,
+}
+
+/// Turns any listing endpoint into a Stream that follows the `after` cursor.
+///
+/// `fetch` is called with the FeedOption for each page. The stream ends when Reddit stops returning
+/// an `after` cursor, a page is empty, `max_items` have been yielded or an error is returned.
+/// ```no_run
+/// #[tokio::main]
+/// async fn main() ->anyhow::Result<()>{
+///    use futures_util::TryStreamExt;
+///    use rraw::auth::AnonymousAuthenticator;
+///    use rraw::responses::listing::paginator::paginate;
+///    use rraw::submission::SubmissionRetriever;
+///    use rraw::Client;
+///    let client = Client:: login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
+///    let subreddit = client.subreddit("rust").await?;
+///    let submissions: Vec<_> = paginate(None, Some(250), |feed| {
+///         subreddit.get_submissions("new", Some(feed))
+///    })
+///    .try_collect()
+///    .await?;
+///    Ok(())
+/// }
+/// ```
+pub fn paginate<'a, T, F, Fut>(
+    feed: Option<FeedOption>,
+    max_items: Option<usize>,
+    fetch: F,
+) -> impl Stream<Item = Result<GenericResponse<T>, Error>> + 'a
+where
+    T: Debug + 'a,
+    F: FnMut(FeedOption) -> Fut + 'a,
+    Fut: Future<Output = Result<GenericListing<T>, Error>> + 'a,
+{
+    let state = PageState {
+        fetch,
+        feed: Some(feed.unwrap_or_default()),
+        buffer: VecDeque::new(),
+        remaining: max_items,
+    };
+    stream::try_unfold(state, |mut state| async move {
+        loop {
+            if state.remaining == Some(0) {
+                return Ok(None);
+            }
+            if let Some(item) = state.buffer.pop_front() {
+                state.remaining = state.remaining.map(|remaining| remaining - 1);
+                return Ok(Some((item, state)));
+            }
+            let mut feed = match state.feed.take() {
+                Some(feed) => feed,
+                None => return Ok(None),
+            };
+            let listing = (state.fetch)(feed.clone()).await?.data;
+            let loaded = listing.children.len() as u32;
+            if loaded > 0 {
+                if let Some(after) = listing.after.filter(|after| !after.is_empty()) {
+                    feed.count = Some(feed.count.unwrap_or(0) + loaded);
+                    feed.after = Some(after);
+                    state.feed = Some(feed);
+                }
+            }
+            state.buffer.extend(listing.children);
+            if state.buffer.is_empty() {
+                return Ok(None);
+            }
+        }
+    })
+}
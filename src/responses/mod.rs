@@ -1,12 +1,17 @@
 use crate::comments::response::CommentResponse;
+use crate::comments::tree::MoreChildren;
 use crate::error::reddit_error::RedditError;
 use crate::error::reddit_error::RedditError::InvalidDataType;
 use crate::message::response::Message;
+use crate::responses::kinds::{
+    Award, LabeledMulti, LiveUpdate, ModAction, Stylesheet, TrophyList, UserList, WikiPage,
+};
 use crate::responses::listing::Listing;
 use crate::Error;
-use serde::de::Error as DeError;
+use serde::de::{DeserializeOwned, Error as DeError};
 pub use serde::Deserialize;
 use serde::Deserializer;
+use serde_json::Value;
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
@@ -15,7 +20,10 @@ use crate::submission::response::SubmissionResponse;
 use crate::subreddit::response::AboutSubreddit;
 use crate::user::response::AboutUser;
 
+pub mod api;
+pub mod kinds;
 pub mod listing;
+pub mod reports;
 
 /// A Generic Response from Reddit the type is pre determined by API
 /// Data from Reddit usually follows this format
@@ -33,6 +41,7 @@ impl<T: Debug> Debug for GenericResponse<T> {
     }
 }
 /// An Enum To Represent the Different Types of Data Reddit will respond with
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RedditDataType {
     /// Type: `Listing`
     Listing,
@@ -48,6 +57,24 @@ pub enum RedditDataType {
     Subreddit,
     /// Type: `t6`
     Award,
+    /// Type: `more`. Comments that were left out
+    More,
+    /// Type: `LabeledMulti`
+    LabeledMulti,
+    /// Type: `modaction`
+    ModAction,
+    /// Type: `UserList`
+    UserList,
+    /// Type: `TrophyList`
+    TrophyList,
+    /// Type: `wikipage`
+    WikiPage,
+    /// Type: `stylesheet`
+    Stylesheet,
+    /// Type: `LiveUpdate`
+    LiveUpdate,
+    /// A kind rraw does not know. Only created by [Lenient]
+    Unknown,
 }
 impl Display for RedditDataType {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -59,6 +86,15 @@ impl Display for RedditDataType {
             RedditDataType::Subreddit => "t5",
             RedditDataType::Award => "t6",
             RedditDataType::Listing => "Listing",
+            RedditDataType::More => "more",
+            RedditDataType::LabeledMulti => "LabeledMulti",
+            RedditDataType::ModAction => "modaction",
+            RedditDataType::UserList => "UserList",
+            RedditDataType::TrophyList => "TrophyList",
+            RedditDataType::WikiPage => "wikipage",
+            RedditDataType::Stylesheet => "stylesheet",
+            RedditDataType::LiveUpdate => "LiveUpdate",
+            RedditDataType::Unknown => "Unknown",
         };
         write!(f, "{}", data)
     }
@@ -74,8 +110,17 @@ impl FromStr for RedditDataType {
             "t3" => Ok(RedditDataType::Link),
             "t4" => Ok(RedditDataType::Message),
             "t5" => Ok(RedditDataType::Subreddit),
-            "t6" => Ok(RedditDataType::Message),
+            "t6" => Ok(RedditDataType::Award),
             "Listing" => Ok(RedditDataType::Listing),
+            "more" => Ok(RedditDataType::More),
+            "LabeledMulti" => Ok(RedditDataType::LabeledMulti),
+            "modaction" => Ok(RedditDataType::ModAction),
+            "UserList" => Ok(RedditDataType::UserList),
+            "TrophyList" => Ok(RedditDataType::TrophyList),
+            "wikipage" => Ok(RedditDataType::WikiPage),
+            "stylesheet" => Ok(RedditDataType::Stylesheet),
+            "LiveUpdate" => Ok(RedditDataType::LiveUpdate),
+
             data => Err(InvalidDataType(data.to_string())),
         }
     }
@@ -101,8 +146,18 @@ impl From<RedditTypeResponse> for RedditResponse {
             RedditTypeResponse::Link(_) => RedditDataType::Link,
             RedditTypeResponse::Message(_) => RedditDataType::Message,
             RedditTypeResponse::Subreddit(_) => RedditDataType::Subreddit,
-            RedditTypeResponse::Award => RedditDataType::Award,
+            RedditTypeResponse::Award(_) => RedditDataType::Award,
             RedditTypeResponse::Listing(_) => RedditDataType::Listing,
+            RedditTypeResponse::More(_) => RedditDataType::More,
+            RedditTypeResponse::LabeledMulti(_) => RedditDataType::LabeledMulti,
+            RedditTypeResponse::ModAction(_) => RedditDataType::ModAction,
+            RedditTypeResponse::UserList(_) => RedditDataType::UserList,
+            RedditTypeResponse::TrophyList(_) => RedditDataType::TrophyList,
+            RedditTypeResponse::WikiPage(_) => RedditDataType::WikiPage,
+            RedditTypeResponse::Stylesheet(_) => RedditDataType::Stylesheet,
+            RedditTypeResponse::LiveUpdate(_) => RedditDataType::LiveUpdate,
+
+            RedditTypeResponse::Unknown { .. } => RedditDataType::Unknown,
         };
         RedditResponse {
             kind,
@@ -111,40 +166,113 @@ impl From<RedditTypeResponse> for RedditResponse {
     }
 }
 
+/// Deserializes `T` keeping things with a kind rraw does not know as [RedditTypeResponse::Unknown] instead of failing.
+///
+/// Used by [ClientBuilder::lenient](crate::builder::ClientBuilder::lenient)
+/// ```rust
+/// use rraw::responses::{Lenient, RedditResponse, RedditTypeResponse};
+/// let json = r#"{"kind": "t9", "data": {"id": "abc"}}"#;
+/// assert!(serde_json::from_str::<RedditResponse>(json).is_err());
+/// let Lenient(response) = serde_json::from_str::<Lenient<RedditResponse>>(json).unwrap();
+/// assert!(matches!(response.data, RedditTypeResponse::Unknown { .. }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lenient<T>(pub T);
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Lenient<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut value = Value::deserialize(deserializer)?;
+        wrap_unknown_kinds(&mut value);
+        T::deserialize(value).map(Lenient).map_err(DeError::custom)
+    }
+}
+
+/// The kind [Lenient] wraps things with an unknown kind in. Its data is the original thing
+const UNKNOWN_KIND: &str = "Unknown";
+
+/// Wraps every thing with a kind rraw does not know into a thing of the kind [UNKNOWN_KIND]
+fn wrap_unknown_kinds(value: &mut Value) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(wrap_unknown_kinds),
+        Value::Object(object) => {
+            let unknown = object.len() == 2
+                && object.contains_key("data")
+                && matches!(object.get("kind"), Some(Value::String(kind)) if RedditDataType::from_str(kind).is_err());
+            if unknown {
+                let thing = std::mem::take(object);
+                object.insert("kind".to_string(), Value::from(UNKNOWN_KIND));
+                object.insert("data".to_string(), Value::Object(thing));
+            } else {
+                object.values_mut().for_each(wrap_unknown_kinds);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Deserializes the data of a kind. Errors include the kind and the path of the field
+fn from_data<T: DeserializeOwned, E: DeError>(kind: &str, data: Value) -> Result<T, E> {
+    serde_path_to_error::deserialize(data).map_err(|error| {
+        let path = error.path().to_string();
+        let path = if path == "." {
+            "data".to_string()
+        } else {
+            format!("data.{path}")
+        };
+        E::custom(format!("Invalid {kind} at {path}: {}", error.inner()))
+    })
+}
+
 impl<'de> Deserialize<'de> for RedditResponse {
     fn deserialize<D>(deserializer: D) -> Result<RedditResponse, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let value: serde_json::Value = serde::Deserialize::deserialize(deserializer).unwrap();
-        if let Some(kind) = value["kind"].as_str() {
-            return match kind {
-                "t1" => Ok(RedditResponse::from(RedditTypeResponse::Comment(
-                    serde_json::from_value(value["data"].clone()).unwrap(),
-                ))),
-                "t2" => Ok(RedditResponse::from(RedditTypeResponse::Account(
-                    serde_json::from_value(value["data"].clone()).unwrap(),
-                ))),
-                "t3" => Ok(RedditResponse::from(RedditTypeResponse::Link(
-                    serde_json::from_value(value["data"].clone()).unwrap(),
-                ))),
-                "t4" => Ok(RedditResponse::from(RedditTypeResponse::Message(
-                    serde_json::from_value(value["data"].clone()).unwrap(),
-                ))),
-                "t5" => Ok(RedditResponse::from(RedditTypeResponse::Subreddit(
-                    serde_json::from_value(value["data"].clone()).unwrap(),
-                ))),
-                "Listing" => Ok(RedditResponse::from(RedditTypeResponse::Listing(
-                    serde_json::from_value(value["data"].clone()).unwrap(),
-                ))),
-                //"t6" => { Ok(GenericResponse::new(RedditType::Comment(serde_json::from_str(value["data"].as_str().unwrap()).unwrap()))) }
-                _ => Err(DeError::custom("Invalid Reddit Kind")),
-            };
-        }
-        Err(DeError::custom("Some how we are missing a kind tag"))
+        let mut value = match Value::deserialize(deserializer)? {
+            Value::Object(value) => value,
+            _ => return Err(DeError::custom("Expected an object with a kind and data")),
+        };
+        let kind = match value.remove("kind") {
+            Some(Value::String(kind)) => kind,
+            _ => return Err(DeError::custom("Some how we are missing a kind tag")),
+        };
+        let data = value.remove("data").unwrap_or_default();
+        let response = match kind.as_str() {
+            "t1" => RedditTypeResponse::Comment(from_data(&kind, data)?),
+            "t2" => RedditTypeResponse::Account(from_data(&kind, data)?),
+            "t3" => RedditTypeResponse::Link(from_data(&kind, data)?),
+            "t4" => RedditTypeResponse::Message(from_data(&kind, data)?),
+            "t5" => RedditTypeResponse::Subreddit(from_data(&kind, data)?),
+            "t6" => RedditTypeResponse::Award(from_data(&kind, data)?),
+            "Listing" => RedditTypeResponse::Listing(from_data(&kind, data)?),
+            "more" => RedditTypeResponse::More(from_data(&kind, data)?),
+            "LabeledMulti" => RedditTypeResponse::LabeledMulti(from_data(&kind, data)?),
+            "modaction" => RedditTypeResponse::ModAction(from_data(&kind, data)?),
+            "UserList" => RedditTypeResponse::UserList(from_data(&kind, data)?),
+            "TrophyList" => RedditTypeResponse::TrophyList(from_data(&kind, data)?),
+            "wikipage" => RedditTypeResponse::WikiPage(from_data(&kind, data)?),
+            "stylesheet" => RedditTypeResponse::Stylesheet(from_data(&kind, data)?),
+            "LiveUpdate" => RedditTypeResponse::LiveUpdate(from_data(&kind, data)?),
+
+            UNKNOWN_KIND => match data {
+                Value::Object(mut thing) => match (thing.remove("kind"), thing.remove("data")) {
+                    (Some(Value::String(kind)), Some(data)) => {
+                        RedditTypeResponse::Unknown { kind, data }
+                    }
+                    _ => return Err(DeError::custom("Invalid Unknown at data")),
+                },
+                _ => return Err(DeError::custom("Invalid Unknown at data")),
+            },
+            _ => return Err(DeError::custom(format!("Invalid Reddit Kind {kind}"))),
+        };
+        Ok(RedditResponse::from(response))
     }
 }
 /// FullNames are the {t1,t2,t3,t4,t5,t6}_{id} you see within Reddit API all the time
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct FullName {
     pub reddit_type: RedditDataType,
     pub id: String,
@@ -169,10 +297,10 @@ impl FromStr for FullName {
             // Yes, it is always a good time to make a monty python joke.
             return Err(Error::from("Then shalt thou count to two, no more, no less. Two shall be the number thou shalt count, and the number of the counting shall be two."));
         }
-        return Ok(FullName {
+        Ok(FullName {
             reddit_type: RedditDataType::from_str(split.first().unwrap())?,
             id: split.get(1).unwrap().to_string(),
-        });
+        })
     }
 }
 
@@ -186,6 +314,12 @@ impl Debug for FullName {
         write!(f, "{}_{}", self.reddit_type, self.id)
     }
 }
+
+/// Data that is identified by a FullName
+pub trait HasFullName {
+    /// The FullName of the data
+    fn full_name(&self) -> FullName;
+}
 /// Reddit Type Response Enum
 pub enum RedditTypeResponse {
     /// The Listing Type
@@ -202,8 +336,35 @@ pub enum RedditTypeResponse {
     /// About SubReddit Response
     /// Boxed for Memory Safety
     Subreddit(Box<AboutSubreddit>),
-    /// TODO
-    Award,
+    /// Trophy Response
+    Award(Award),
+    /// A `more` stub
+    More(MoreChildren),
+    /// Custom Feed Response
+    /// Boxed for Memory Safety
+    LabeledMulti(Box<LabeledMulti>),
+    /// Moderation Log Entry
+    /// Boxed for Memory Safety
+    ModAction(Box<ModAction>),
+    /// User List Response
+    UserList(UserList),
+    /// Trophy List Response
+    TrophyList(TrophyList),
+    /// Wiki Page Response
+    /// Boxed for Memory Safety
+    WikiPage(Box<WikiPage>),
+    /// Stylesheet Response
+    Stylesheet(Stylesheet),
+    /// Live Thread Update
+    /// Boxed for Memory Safety
+    LiveUpdate(Box<LiveUpdate>),
+    /// A kind rraw does not know. Only created by [Lenient]
+    Unknown {
+        /// The kind value from Reddit
+        kind: String,
+        /// The raw data
+        data: Value,
+    },
 }
 
 impl Debug for RedditTypeResponse {
@@ -227,8 +388,36 @@ impl Debug for RedditTypeResponse {
             RedditTypeResponse::Subreddit(data) => {
                 write!(f, "{:?}", data)
             }
-            RedditTypeResponse::Award => {
-                write!(f, "AWARD!")
+            RedditTypeResponse::Award(data) => {
+                write!(f, "{:?}", data)
+            }
+            RedditTypeResponse::More(data) => {
+                write!(f, "{:?}", data)
+            }
+            RedditTypeResponse::LabeledMulti(data) => {
+                write!(f, "{:?}", data)
+            }
+            RedditTypeResponse::ModAction(data) => {
+                write!(f, "{:?}", data)
+            }
+            RedditTypeResponse::UserList(data) => {
+                write!(f, "{:?}", data)
+            }
+            RedditTypeResponse::TrophyList(data) => {
+                write!(f, "{:?}", data)
+            }
+            RedditTypeResponse::WikiPage(data) => {
+                write!(f, "{:?}", data)
+            }
+            RedditTypeResponse::Stylesheet(data) => {
+                write!(f, "{:?}", data)
+            }
+            RedditTypeResponse::LiveUpdate(data) => {
+                write!(f, "{:?}", data)
+            }
+
+            RedditTypeResponse::Unknown { kind, .. } => {
+                write!(f, "Unknown kind {}", kind)
             }
         }
     }
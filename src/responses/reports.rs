@@ -0,0 +1,74 @@
+//! The reports on submissions and comments. Only visible to moderators
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// A report by a moderator. Reddit sends it as `[reason, moderator]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModReport {
+    pub reason: String,
+    /// The moderator that reported it
+    pub moderator: String,
+}
+
+impl<'de> Deserialize<'de> for ModReport {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values: Vec<Value> = Vec::deserialize(deserializer)?;
+        let string = |index: usize| {
+            values
+                .get(index)
+                .and_then(Value::as_str)
+                .map(ToString::to_string)
+        };
+        Ok(ModReport {
+            reason: string(0).unwrap_or_default(),
+            moderator: string(1).ok_or_else(|| DeError::custom("Missing Report Moderator"))?,
+        })
+    }
+}
+
+/// Reports by users grouped by reason. Reddit sends it as `[reason, count, snoozed, can_snooze]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserReport {
+    /// None if the reason is hidden
+    pub reason: Option<String>,
+    /// How many users reported it for this reason
+    pub count: u64,
+    /// If reports with this reason are snoozed
+    pub snoozed: bool,
+    pub can_snooze: bool,
+}
+
+impl<'de> Deserialize<'de> for UserReport {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values: Vec<Value> = Vec::deserialize(deserializer)?;
+        let flag = |index: usize| values.get(index).and_then(Value::as_bool).unwrap_or(false);
+        Ok(UserReport {
+            reason: values
+                .first()
+                .and_then(Value::as_str)
+                .map(ToString::to_string),
+            count: values
+                .get(1)
+                .and_then(Value::as_u64)
+                .ok_or_else(|| DeError::custom("Missing Report Count"))?,
+            snoozed: flag(2),
+            can_snooze: flag(3),
+        })
+    }
+}
+
+/// Reddit sends `null` instead of an empty list to users that are not moderators
+pub(crate) fn deserialize_reports<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
@@ -0,0 +1,85 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+
+/// How the Client retries requests that failed for a transient reason.
+///
+/// Retries happen on 429, 500, 502, 503 and 504 responses. And on connection errors or timeouts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The total number of attempts. Including the first. 1 disables retrying
+    pub max_attempts: u32,
+    /// The wait before the first retry. Doubled every retry after
+    pub initial_backoff: Duration,
+    /// The longest the Client will wait between attempts
+    pub max_backoff: Duration,
+    /// Randomizes the wait so multiple clients do not retry at the same time
+    pub jitter: bool,
+    /// Retry non-idempotent requests such as POST. Only enable if the endpoints you call are safe to send twice
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+    /// Is the status worth retrying
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+    /// Is the error worth retrying
+    pub fn is_retryable_error(error: &reqwest::Error) -> bool {
+        error.is_connect() || error.is_timeout()
+    }
+    /// The wait before the retry following `attempt`. Starting at 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        if self.jitter {
+            // Equal Jitter. Half of the backoff is kept the other half is random
+            let half = backoff / 2;
+            let random = RandomState::new().build_hasher().finish();
+            half + Duration::from_nanos(random % (half.as_nanos() as u64 + 1))
+        } else {
+            backoff
+        }
+    }
+    /// The wait requested by Reddit's `Retry-After` header or the backoff.
+    pub fn delay(&self, attempt: u32, headers: &HeaderMap) -> Duration {
+        headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(|seconds| Duration::from_secs(seconds).min(self.max_backoff))
+            .unwrap_or_else(|| self.backoff(attempt))
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Debug;
+use std::future::Future;
+use std::time::Duration;
+
+use futures_util::future;
+use futures_util::stream::{self, Stream};
+use log::trace;
+
+use crate::error::Error;
+use crate::responses::listing::GenericListing;
+use crate::responses::{FullName, GenericResponse, HasFullName};
+use crate::utils::options::FeedOption;
+
+/// Settings for streams that poll a listing for new items
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// The wait between polls while new items keep arriving
+    pub min_interval: Duration,
+    /// The longest wait between polls when nothing new arrives
+    pub max_interval: Duration,
+    /// How many FullNames are remembered to detect duplicates
+    pub seen_capacity: usize,
+    /// Items per poll. Reddit caps this at 100
+    pub limit: u32,
+    /// Do not yield the items that already exist on the first poll
+    pub skip_existing: bool,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions {
+            min_interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(16),
+            seen_capacity: 301,
+            limit: 100,
+            skip_existing: false,
+        }
+    }
+}
+
+/// Remembers the most recent FullNames. The oldest are forgotten first
+pub(crate) struct SeenSet {
+    set: HashSet<FullName>,
+    order: VecDeque<FullName>,
+    capacity: usize,
+}
+
+impl SeenSet {
+    pub(crate) fn new(capacity: usize) -> SeenSet {
+        SeenSet {
+            set: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+    /// Returns true if the FullName had not been seen
+    pub(crate) fn insert(&mut self, full_name: FullName) -> bool {
+        if self.set.contains(&full_name) {
+            return false;
+        }
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.set.remove(&oldest);
+            }
+        }
+        self.set.insert(full_name.clone());
+        self.order.push_back(full_name);
+        true
+    }
+    /// Forgets the FullName. So it is new again
+    pub(crate) fn remove(&mut self, full_name: &FullName) {
+        if self.set.remove(full_name) {
+            self.order.retain(|seen| seen != full_name);
+        }
+    }
+}
+
+struct PollState<F, H, T: Debug> {
+    fetch: F,
+    before_yield: H,
+    options: StreamOptions,
+    seen: SeenSet,
+    buffer: VecDeque<GenericResponse<T>>,
+    interval: Duration,
+    first_poll: bool,
+}
+
+/// Polls a listing that is sorted newest first. Yielding every item once, oldest first.
+///
+/// The wait between polls is reset to `min_interval` when new items arrive and doubles up to
+/// `max_interval` when nothing new arrives. Errors are yielded and polling continues.
+/// The stream never ends on its own.
+pub fn poll_new<'a, T, F, Fut>(
+    options: StreamOptions,
+    fetch: F,
+) -> impl Stream<Item = Result<GenericResponse<T>, Error>> + 'a
+where
+    T: HasFullName + Debug + 'a,
+    F: FnMut(FeedOption) -> Fut + 'a,
+    Fut: Future<Output = Result<GenericListing<T>, Error>> + 'a,
+{
+    poll_new_with(options, fetch, |_| future::ready(Ok(())))
+}
+
+/// Like [poll_new]. `before_yield` receives the FullNames of the new items of a poll, oldest first,
+/// before any of them is yielded.
+///
+/// If it fails the error is yielded instead of the items. They are fetched again on the next poll
+pub fn poll_new_with<'a, T, F, Fut, H, HFut>(
+    options: StreamOptions,
+    fetch: F,
+    before_yield: H,
+) -> impl Stream<Item = Result<GenericResponse<T>, Error>> + 'a
+where
+    T: HasFullName + Debug + 'a,
+    F: FnMut(FeedOption) -> Fut + 'a,
+    Fut: Future<Output = Result<GenericListing<T>, Error>> + 'a,
+    H: FnMut(Vec<FullName>) -> HFut + 'a,
+    HFut: Future<Output = Result<(), Error>> + 'a,
+{
+    let state = PollState {
+        fetch,
+        before_yield,
+        seen: SeenSet::new(options.seen_capacity),
+        buffer: VecDeque::new(),
+        interval: options.min_interval,
+        first_poll: true,
+        options,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.buffer.pop_front() {
+                return Some((Ok(item), state));
+            }
+            if !state.first_poll {
+                crate::utils::sleep(state.interval).await;
+            }
+            let feed = FeedOption {
+                limit: Some(state.options.limit),
+                ..FeedOption::default()
+            };
+            let result = (state.fetch)(feed).await;
+            let first_poll = std::mem::replace(&mut state.first_poll, false);
+            let listing = match result {
+                Ok(listing) => listing.data,
+                Err(error) => {
+                    state.interval = state.options.max_interval;
+                    return Some((Err(error), state));
+                }
+            };
+            let seen = &mut state.seen;
+            let mut new: Vec<_> = listing
+                .children
+                .into_iter()
+                .filter(|item| seen.insert(item.data.full_name()))
+                .collect();
+            if first_poll && state.options.skip_existing {
+                new.clear();
+            }
+            state.interval = if new.is_empty() {
+                (state.interval * 2).min(state.options.max_interval)
+            } else {
+                state.options.min_interval
+            };
+            trace!(
+                "Found {} new items. Next poll in {:?}",
+                new.len(),
+                state.interval
+            );
+            if new.is_empty() {
+                continue;
+            }
+            let full_names: Vec<FullName> =
+                new.iter().rev().map(|item| item.data.full_name()).collect();
+            if let Err(error) = (state.before_yield)(full_names.clone()).await {
+                for full_name in &full_names {
+                    state.seen.remove(full_name);
+                }
+                state.interval = state.options.max_interval;
+                return Some((Err(error), state));
+            }
+            state.buffer.extend(new.into_iter().rev());
+        }
+    })
+}
@@ -1,6 +1,7 @@
 pub mod response;
 
 use crate::auth::Authenticator;
+use crate::comments::tree::CommentTree;
 use crate::comments::CommentRetriever;
 use crate::submission::response::SubmissionsResponse;
 use crate::utils::options::{CommentOption, FeedOption};
@@ -44,6 +45,11 @@ impl<'a, A: Authenticator, T: SubmissionType<'a>> CommentRetriever for Submissio
         }
         return self.me.get_json::<ListingArray>(&path, false, false).await;
     }
+    async fn get_comment_tree(&self, sort: Option<CommentOption>) -> Result<CommentTree, Error> {
+        self.me
+            .comment_tree(self.submission.get_permalink(), sort)
+            .await
+    }
 }
 
 pub type Submissions<'a, A, T> = GenericListing<Submission<'a, A, T>>;
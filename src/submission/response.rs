@@ -1,4 +1,6 @@
 use crate::responses::listing::GenericListing;
+use crate::responses::reports::{deserialize_reports, ModReport, UserReport};
+use crate::responses::{FullName, HasFullName, RedditDataType};
 use serde::Deserialize;
 use std::fmt::{Debug, Formatter};
 
@@ -29,6 +31,12 @@ pub struct SubmissionResponse {
     pub title: String,
     pub created_utc: f64,
     pub distinguished: Option<String>,
+    /// The number of reports. Only visible to moderators
+    pub num_reports: Option<i64>,
+    #[serde(default, deserialize_with = "deserialize_reports")]
+    pub mod_reports: Vec<ModReport>,
+    #[serde(default, deserialize_with = "deserialize_reports")]
+    pub user_reports: Vec<UserReport>,
 }
 
 impl Debug for SubmissionResponse {
@@ -47,4 +55,13 @@ impl<'a> SubmissionType<'a> for SubmissionResponse {
     }
 }
 
+impl HasFullName for SubmissionResponse {
+    fn full_name(&self) -> FullName {
+        FullName {
+            reddit_type: RedditDataType::Link,
+            id: self.id.clone(),
+        }
+    }
+}
+
 pub type SubmissionsResponse = GenericListing<SubmissionResponse>;
@@ -1,17 +1,25 @@
 pub mod response;
+pub mod submit;
 
 use log::trace;
-use reqwest::Body;
 
-use crate::submission::response::SubmissionsResponse;
+use crate::comments::response::CommentResponse;
+use crate::responses::kinds::ModAction;
+use crate::responses::listing::{GenericListing, RedditListing};
+use crate::responses::GenericResponse;
+use crate::stream::StreamOptions;
+use crate::submission::response::{SubmissionResponse, SubmissionsResponse};
 use crate::submission::SubmissionRetriever;
 use crate::{Authorized, Client};
 
 use crate::auth::Authenticator;
 use crate::error::Error;
 use crate::subreddit::response::{AboutSubreddit, Contributors, Friend, Moderators};
-use crate::utils::options::{FeedOption, FriendType};
+use crate::subreddit::submit::{Submit, SubmittedPost};
+use crate::utils::options::{FeedOption, FriendType, ModQueueFilter};
+use crate::utils::request::ApiRequest;
 use async_trait::async_trait;
+use futures_util::Stream;
 use serde_json::Value;
 
 /// Subreddit Object
@@ -32,23 +40,35 @@ impl<'a, A: Authenticator> Subreddit<'a, A> {
     /// Returns a Listing of "Contributors" to the Subreddit
     /// Returns 403 if the contributors are displayed. Most Subreddits will have this disabled
     pub async fn get_contributors(&self, feed: Option<FeedOption>) -> Result<Contributors, Error> {
-        let mut string = format!("/r/{}/about/contributors", &self.subreddit);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request =
+            ApiRequest::new(format!("/r/{}/about/contributors", &self.subreddit)).feed(feed);
         self.me
-            .get_json::<Contributors>(&*string, true, false)
+            .get_json::<Contributors>(&request.url(), true, false)
             .await
     }
     /// Returns a Listing of Moderators to the Subreddit
     pub async fn get_moderators(&self, feed: Option<FeedOption>) -> Result<Moderators, Error> {
-        let mut string = format!("/r/{}/about/moderators", &self.subreddit);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
-        self.me.get_json::<Moderators>(&string, true, false).await
+        let request =
+            ApiRequest::new(format!("/r/{}/about/moderators", &self.subreddit)).feed(feed);
+        self.me
+            .get_json::<Moderators>(&request.url(), true, false)
+            .await
+    }
+    /// Streams new submissions to the Subreddit as they are posted. See [Client::stream_submissions]
+    pub fn stream_submissions(
+        &self,
+        options: Option<StreamOptions>,
+    ) -> impl Stream<Item = Result<GenericResponse<SubmissionResponse>, Error>> + 'a {
+        self.me
+            .stream_submissions(self.subreddit.display_name.clone(), options)
+    }
+    /// Streams new comments to the Subreddit as they are posted. See [Client::stream_comments]
+    pub fn stream_comments(
+        &self,
+        options: Option<StreamOptions>,
+    ) -> impl Stream<Item = Result<GenericResponse<CommentResponse>, Error>> + 'a {
+        self.me
+            .stream_comments(self.subreddit.display_name.clone(), options)
     }
 }
 
@@ -61,31 +81,150 @@ impl<'a, A: Authorized> Subreddit<'a, A> {
             &self.subreddit,
             &typ
         );
-        let string = format!("/r/{}/api/friend", &self.subreddit);
-
-        let body = Body::from(format!("name={}&type={}", username, typ));
-        self.me.post_json::<Friend>(&string, true, body).await
+        let request = ApiRequest::new(format!("/r/{}/api/friend", &self.subreddit))
+            .form("name", username)
+            .form("type", typ);
+        self.me
+            .post_json::<Friend>(&request.url(), true, request.body())
+            .await
     }
     ///  removes a friend from the Subreddit
     pub async fn remove_friend(&self, username: String, typ: FriendType) -> Result<Friend, Error> {
-        let string = format!("/r/{}/api/unfriend", &self.subreddit);
+        let request = ApiRequest::new(format!("/r/{}/api/unfriend", &self.subreddit))
+            .form("name", username)
+            .form("type", typ);
+        self.me
+            .post_json::<Friend>(&request.url(), true, request.body())
+            .await
+    }
+
+    /// Submits a new post to the Subreddit
+    /// ```no_run
+    /// #[tokio::main]
+    /// async fn main() ->anyhow::Result<()>{
+    ///    use std::env;
+    ///    use rraw::auth::PasswordAuthenticator;
+    ///    use rraw::subreddit::submit::Submit;
+    ///    use rraw::Client;
+    ///    let client = Client:: login(PasswordAuthenticator::new(env::var("CLIENT_ID")?,env::var("CLIENT_SECRET")?,env::var("USERNAME")?,env::var("PASSWORD")?), "RRAW Test (by u/KingTuxWH)").await?;
+    ///    let subreddit = client.subreddit("test").await?;
+    ///    let post = subreddit.submit(Submit::self_post("Hello", "World").spoiler(true)).await?;
+    ///    println!("{}", post.url);
+    ///    Ok(())
+    /// }
+    /// ```
+    pub async fn submit(&self, submission: Submit) -> Result<SubmittedPost, Error> {
+        trace!(
+            "Submitting {:?} to r/{}",
+            &submission.title,
+            &self.subreddit
+        );
+        let body = submission.params(&self.subreddit.display_name);
+        self.me
+            .post_api::<SubmittedPost>("/api/submit", body.into())
+            .await?
+            .ok_or_else(|| Error::from("Reddit did not return the submission"))
+    }
 
-        let body = Body::from(format!("name={username}&type={typ}"));
-        self.me.post_json::<Friend>(&string, true, body).await
+    /// Returns the moderation log. Requires moderator permissions
+    pub async fn mod_log(
+        &self,
+        feed: Option<FeedOption>,
+    ) -> Result<GenericListing<ModAction>, Error> {
+        let request = ApiRequest::new(format!("/r/{}/about/log", &self.subreddit)).feed(feed);
+        self.me
+            .get_json::<GenericListing<ModAction>>(&request.url(), true, false)
+            .await
     }
 
+    /// Submissions and comments waiting for a moderator. Reported, filtered or removed by the spam filter.
+    /// Requires moderator permissions
+    /// ```no_run
+    /// #[tokio::main]
+    /// async fn main() ->anyhow::Result<()>{
+    ///    use std::env;
+    ///    use rraw::auth::PasswordAuthenticator;
+    ///    use rraw::responses::RedditTypeResponse;
+    ///    use rraw::utils::options::ModQueueFilter;
+    ///    use rraw::Client;
+    ///    let client = Client:: login(PasswordAuthenticator::new(env::var("CLIENT_ID")?,env::var("CLIENT_SECRET")?,env::var("USERNAME")?,env::var("PASSWORD")?), "RRAW Test (by u/KingTuxWH)").await?;
+    ///    let subreddit = client.subreddit("test").await?;
+    ///    for thing in subreddit.modqueue(Some(ModQueueFilter::Links), None).await?.data.children {
+    ///        if let RedditTypeResponse::Link(submission) = thing.data {
+    ///            println!("{} {:?}", submission.title, submission.user_reports);
+    ///        }
+    ///    }
+    ///    Ok(())
+    /// }
+    /// ```
+    pub async fn modqueue(
+        &self,
+        only: Option<ModQueueFilter>,
+        feed: Option<FeedOption>,
+    ) -> Result<RedditListing, Error> {
+        self.mod_listing("modqueue", only, feed).await
+    }
+    /// Reported submissions and comments. Requires moderator permissions
+    pub async fn reports(
+        &self,
+        only: Option<ModQueueFilter>,
+        feed: Option<FeedOption>,
+    ) -> Result<RedditListing, Error> {
+        self.mod_listing("reports", only, feed).await
+    }
+    /// Submissions and comments removed as spam. Requires moderator permissions
+    pub async fn spam(
+        &self,
+        only: Option<ModQueueFilter>,
+        feed: Option<FeedOption>,
+    ) -> Result<RedditListing, Error> {
+        self.mod_listing("spam", only, feed).await
+    }
+    /// Recently edited submissions and comments. Requires moderator permissions
+    pub async fn edited(
+        &self,
+        only: Option<ModQueueFilter>,
+        feed: Option<FeedOption>,
+    ) -> Result<RedditListing, Error> {
+        self.mod_listing("edited", only, feed).await
+    }
+    /// Submissions no moderator has approved or removed yet. Requires moderator permissions
+    pub async fn unmoderated(
+        &self,
+        only: Option<ModQueueFilter>,
+        feed: Option<FeedOption>,
+    ) -> Result<RedditListing, Error> {
+        self.mod_listing("unmoderated", only, feed).await
+    }
+    /// `/r/{subreddit}/about/{location}`
+    async fn mod_listing(
+        &self,
+        location: &str,
+        only: Option<ModQueueFilter>,
+        feed: Option<FeedOption>,
+    ) -> Result<RedditListing, Error> {
+        let request = ApiRequest::new(format!("/r/{}/about/{}", &self.subreddit, location))
+            .query_opt("only", only)
+            .feed(feed);
+        self.me
+            .get_json::<RedditListing>(&request.url(), true, false)
+            .await
+    }
+
+    /// Composes a message from the Subreddit
     pub async fn compose(
         &self,
         recipient: String,
         subject: String,
         body: String,
     ) -> Result<Value, Error> {
-        let string = format!(
-            "api_type=json&subject={subject}&text={body}&to={recipient}&from_sr={}",
-            self.subreddit.name
-        );
-        let body = reqwest::Body::from(string);
-        self.me.post_json::<Value>("/api/compose", true, body).await
+        let request = ApiRequest::new("/api/compose")
+            .form("api_type", "json")
+            .form("subject", subject)
+            .form("text", body)
+            .form("to", recipient)
+            .form("from_sr", &self.subreddit.name);
+        self.me.post_api_raw(&request.url(), request.body()).await
     }
 }
 #[async_trait(?Send)]
@@ -95,13 +234,11 @@ impl<'a, A: Authenticator> SubmissionRetriever for Subreddit<'a, A> {
         sort: T,
         feed_options: Option<FeedOption>,
     ) -> Result<SubmissionsResponse, Error> {
-        let mut path = format!("/r/{}/{}", &self.subreddit, sort.into());
-        if let Some(options) = feed_options {
-            options.extend(&mut path)
-        }
+        let request =
+            ApiRequest::new(format!("/r/{}/{}", &self.subreddit, sort.into())).feed(feed_options);
         return self
             .me
-            .get_json::<SubmissionsResponse>(&path, false, false)
+            .get_json::<SubmissionsResponse>(&request.url(), false, false)
             .await;
     }
 }
@@ -0,0 +1,125 @@
+use serde::Deserialize;
+
+use crate::responses::FullName;
+use crate::utils::request::Params;
+
+/// What is being submitted
+#[derive(Clone, Debug)]
+pub enum SubmissionKind {
+    /// A self post with the markdown text
+    SelfPost(String),
+    /// A link post to the URL
+    Link(String),
+    /// A crosspost of the submission
+    Crosspost(FullName),
+}
+
+/// A new submission. Created with [Submit::self_post], [Submit::link] or [Submit::crosspost]
+/// ```rust
+/// use rraw::subreddit::submit::Submit;
+/// let submission = Submit::link("RRAW", "https://github.com/wyatt-herkamp/rraw")
+///     .flair_text("Project")
+///     .send_replies(false);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Submit {
+    pub title: String,
+    pub kind: SubmissionKind,
+    pub flair_id: Option<String>,
+    pub flair_text: Option<String>,
+    pub nsfw: bool,
+    pub spoiler: bool,
+    /// Send comment replies to the inbox. Default: true
+    pub send_replies: bool,
+    /// Submit the link even if it has been submitted before. Default: false
+    pub resubmit: bool,
+}
+
+impl Submit {
+    fn new(title: String, kind: SubmissionKind) -> Submit {
+        Submit {
+            title,
+            kind,
+            flair_id: None,
+            flair_text: None,
+            nsfw: false,
+            spoiler: false,
+            send_replies: true,
+            resubmit: false,
+        }
+    }
+    /// A self post
+    pub fn self_post<T: Into<String>, S: Into<String>>(title: T, text: S) -> Submit {
+        Submit::new(title.into(), SubmissionKind::SelfPost(text.into()))
+    }
+    /// A link post
+    pub fn link<T: Into<String>, S: Into<String>>(title: T, url: S) -> Submit {
+        Submit::new(title.into(), SubmissionKind::Link(url.into()))
+    }
+    /// A crosspost of another submission
+    pub fn crosspost<T: Into<String>>(title: T, submission: FullName) -> Submit {
+        Submit::new(title.into(), SubmissionKind::Crosspost(submission))
+    }
+    /// The flair template id
+    pub fn flair_id<S: Into<String>>(mut self, flair_id: S) -> Self {
+        self.flair_id = Some(flair_id.into());
+        self
+    }
+    /// The flair text
+    pub fn flair_text<S: Into<String>>(mut self, flair_text: S) -> Self {
+        self.flair_text = Some(flair_text.into());
+        self
+    }
+    /// Marks the submission NSFW
+    pub fn nsfw(mut self, nsfw: bool) -> Self {
+        self.nsfw = nsfw;
+        self
+    }
+    /// Marks the submission as a spoiler
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.spoiler = spoiler;
+        self
+    }
+    /// Send comment replies to the inbox
+    pub fn send_replies(mut self, send_replies: bool) -> Self {
+        self.send_replies = send_replies;
+        self
+    }
+    /// Submit the link even if it has been submitted before
+    pub fn resubmit(mut self, resubmit: bool) -> Self {
+        self.resubmit = resubmit;
+        self
+    }
+    /// The form body for `/api/submit`
+    pub(crate) fn params(&self, subreddit: &str) -> Params {
+        let params = Params::new()
+            .with("api_type", "json")
+            .with("sr", subreddit)
+            .with("title", &self.title);
+        let params = match &self.kind {
+            SubmissionKind::SelfPost(text) => params.with("kind", "self").with("text", text),
+            SubmissionKind::Link(url) => params.with("kind", "link").with("url", url),
+            SubmissionKind::Crosspost(full_name) => params
+                .with("kind", "crosspost")
+                .with("crosspost_fullname", full_name),
+        };
+        params
+            .with_opt("flair_id", self.flair_id.as_ref())
+            .with_opt("flair_text", self.flair_text.as_ref())
+            .with("nsfw", self.nsfw)
+            .with("spoiler", self.spoiler)
+            .with("sendreplies", self.send_replies)
+            .with("resubmit", self.resubmit)
+    }
+}
+
+/// The submission Reddit created
+#[derive(Deserialize, Debug)]
+pub struct SubmittedPost {
+    /// The FullName of the new submission
+    pub name: FullName,
+    /// The id of the new submission
+    pub id: String,
+    /// The URL of the new submission
+    pub url: String,
+}
@@ -0,0 +1,199 @@
+//! Canned Reddit responses used by [MockReddit](super::MockReddit).
+//!
+//! They only contain the fields rraw reads, so they are also handy for building custom responses.
+use serde_json::{json, Value};
+
+/// Wraps data with its kind. `{"kind": kind, "data": data}`
+pub fn thing(kind: &str, data: Value) -> Value {
+    json!({ "kind": kind, "data": data })
+}
+
+/// A `Listing` of the children
+pub fn listing(children: Vec<Value>, after: Option<&str>) -> Value {
+    thing(
+        "Listing",
+        json!({
+            "modhash": "",
+            "dist": children.len(),
+            "after": after,
+            "before": null,
+            "children": children,
+        }),
+    )
+}
+
+/// A `t1` Comment
+pub fn comment(id: &str, subreddit: &str) -> Value {
+    thing(
+        "t1",
+        json!({
+            "id": id,
+            "name": format!("t1_{id}"),
+            "link_id": "t3_mock01",
+            "parent_id": "t3_mock01",
+            "author": "rraw_test",
+            "author_fullname": "t2_mockuser",
+            "subreddit": subreddit,
+            "subreddit_id": "t5_mocksub",
+            "body": "A mock comment",
+            "body_html": "&lt;p&gt;A mock comment&lt;/p&gt;",
+            "permalink": format!("/r/{subreddit}/comments/mock01/mock_submission/{id}/"),
+            "score": 1.0,
+            "ups": 1,
+            "downs": 0,
+            "likes": null,
+            "stickied": false,
+            "distinguished": null,
+            "created_utc": 1_600_000_000.0,
+            "replies": "",
+        }),
+    )
+}
+
+/// A `t2` Account
+pub fn account(name: &str) -> Value {
+    thing("t2", account_data(name))
+}
+
+fn account_data(name: &str) -> Value {
+    json!({
+        "id": "mockuser",
+        "name": name,
+        "subreddit": null,
+        "snoovatar_size": null,
+        "verified": true,
+        "is_gold": false,
+        "is_mod": false,
+        "has_verified_email": true,
+        "icon_img": "",
+        "hide_from_robots": false,
+        "pref_show_snoovatar": false,
+        "snoovatar_img": "",
+        "accept_followers": true,
+        "has_subscribed": true,
+        "link_karma": 1,
+        "comment_karma": 1,
+        "total_karma": 2,
+        "created": 1_500_000_000.0,
+        "created_utc": 1_500_000_000.0,
+    })
+}
+
+/// The response of `/api/v1/me`. Note: This is not wrapped in a kind
+pub fn me(name: &str) -> Value {
+    let mut data = account_data(name);
+    data["features"] = json!({});
+    data
+}
+
+/// A `t3` Link
+pub fn link(id: &str, subreddit: &str) -> Value {
+    thing(
+        "t3",
+        json!({
+            "id": id,
+            "name": format!("t3_{id}"),
+            "domain": format!("self.{subreddit}"),
+            "subreddit": subreddit,
+            "subreddit_id": "t5_mocksub",
+            "selftext": "A mock submission",
+            "selftext_html": null,
+            "likes": null,
+            "author": "rraw_test",
+            "score": 1.0,
+            "num_comments": 1,
+            "thumbnail": "self",
+            "downs": 0.0,
+            "ups": 1.0,
+            "stickied": false,
+            "locked": false,
+            "over_18": false,
+            "created": 1_600_000_000.0,
+            "created_utc": 1_600_000_000.0,
+            "url": format!("https://www.reddit.com/r/{subreddit}/comments/{id}/mock_submission/"),
+            "permalink": format!("/r/{subreddit}/comments/{id}/mock_submission/"),
+            "title": "Mock Submission",
+            "distinguished": null,
+        }),
+    )
+}
+
+/// A `more` stub hiding the children
+pub fn more(id: &str, parent_id: &str, children: &[&str]) -> Value {
+    thing(
+        "more",
+        json!({
+            "count": children.len(),
+            "name": format!("t1_{id}"),
+            "id": id,
+            "parent_id": parent_id,
+            "depth": 0,
+            "children": children,
+        }),
+    )
+}
+
+/// Adds a moderator report and a user report to a `t1` or `t3`. As moderators see them
+pub fn reported(mut thing: Value) -> Value {
+    thing["data"]["num_reports"] = json!(2);
+    thing["data"]["mod_reports"] = json!([["Breaks rule 1", "rraw_test"]]);
+    thing["data"]["user_reports"] = json!([["Spam", 1, false, true]]);
+    thing
+}
+
+/// A `t4` Message
+pub fn message(id: &str) -> Value {
+    thing(
+        "t4",
+        json!({
+            "id": id,
+            "name": format!("t4_{id}"),
+            "author": "rraw_friend",
+            "author_fullname": "t2_mockfriend",
+            "subject": "Mock Message",
+            "body": "A mock message",
+            "body_html": null,
+            "dest": "rraw_test",
+            "new": true,
+            "likes": null,
+            "score": 0.0,
+            "created": 1_600_000_000.0,
+            "created_utc": 1_600_000_000.0,
+            "was_comment": false,
+            "type": "unknown",
+        }),
+    )
+}
+
+/// A `t5` Subreddit
+pub fn subreddit(name: &str) -> Value {
+    thing(
+        "t5",
+        json!({
+            "name": "t5_mocksub",
+            "display_name": name,
+            "url": format!("/r/{name}/"),
+            "title": format!("r/{name}"),
+            "created": 1_200_000_000.0,
+            "created_utc": 1_200_000_000.0,
+            "subscribers": 1,
+            "over18": false,
+        }),
+    )
+}
+
+/// The response of `/api/v1/access_token`
+pub fn access_token() -> Value {
+    json!({
+        "access_token": "mock_access_token",
+        "expires_in": 3600,
+        "scope": "*",
+        "token_type": "bearer",
+        "refresh_token": "mock_refresh_token",
+    })
+}
+
+/// The `api_type=json` response of a write endpoint without errors
+pub fn api_json(data: Value) -> Value {
+    json!({ "json": { "errors": [], "data": data } })
+}
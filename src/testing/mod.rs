@@ -0,0 +1,400 @@
+//! An in-process stand-in for Reddit. Requires the `testing` feature
+//!
+//! [MockReddit] serves canned fixtures for the read endpoints, the OAuth token endpoints and the
+//! write endpoints. Every request it receives is recorded so tests can assert on it.
+//! ```rust
+//! #[tokio::main]
+//! async fn main() ->anyhow::Result<()>{
+//!    use rraw::auth::AnonymousAuthenticator;
+//!    use rraw::testing::MockReddit;
+//!    let reddit = MockReddit::start()?;
+//!    let client = reddit.client_builder("RRAW Test (by u/KingTuxWH)")
+//!         .login(AnonymousAuthenticator::new())
+//!         .await?;
+//!    let user = client.user("KingTuxWH").await?;
+//!    assert_eq!(user.user.name, "KingTuxWH");
+//!    assert_eq!(reddit.requests()[0].path, "/u/KingTuxWH/about");
+//!    Ok(())
+//! }
+//! ```
+pub mod fixtures;
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::{Debug, Formatter};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use log::trace;
+use serde_json::{json, Value};
+use tokio::sync::oneshot;
+
+use crate::auth::PasswordAuthenticator;
+use crate::builder::{ClientBuilder, Endpoints};
+use crate::error::Error;
+use crate::Client;
+
+/// The user agent of the clients created by [MockReddit::password_client]
+pub const USER_AGENT: &str = "RRAW Test (by u/KingTuxWH)";
+
+/// A request received by [MockReddit]
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    /// The HTTP Method. Example: `GET`
+    pub method: String,
+    /// The path without the query
+    pub path: String,
+    /// The query string without the `?`
+    pub query: Option<String>,
+    /// The headers. Names are lowercase
+    pub headers: HashMap<String, String>,
+    /// The body
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Decodes the `application/x-www-form-urlencoded` body
+    pub fn form(&self) -> HashMap<String, String> {
+        form_urlencoded::parse(self.body.as_bytes())
+            .into_owned()
+            .collect()
+    }
+    /// Decodes the query string
+    pub fn query_pairs(&self) -> HashMap<String, String> {
+        form_urlencoded::parse(self.query.as_deref().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect()
+    }
+    /// Returns the header value
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_lowercase())
+            .map(|value| value.as_str())
+    }
+}
+
+/// A response served by [MockReddit]
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    /// HTTP Status Code
+    pub status: u16,
+    /// Headers to add to the response
+    pub headers: Vec<(String, String)>,
+    /// The body
+    pub body: String,
+}
+
+impl MockResponse {
+    /// A response with the status and body
+    pub fn new<S: Into<String>>(status: u16, body: S) -> MockResponse {
+        MockResponse {
+            status,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+    /// A 200 response with a JSON body
+    pub fn json(body: &Value) -> MockResponse {
+        MockResponse::new(200, body.to_string())
+            .with_header("Content-Type", "application/json; charset=UTF-8")
+    }
+    /// Adds a header to the response
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+    fn not_found() -> MockResponse {
+        MockResponse::new(
+            404,
+            json!({"message": "Not Found", "error": 404}).to_string(),
+        )
+    }
+}
+
+struct MockRoute {
+    method: String,
+    path: String,
+    response: MockResponse,
+    once: bool,
+}
+
+#[derive(Default)]
+struct MockState {
+    requests: Vec<RecordedRequest>,
+    routes: Vec<MockRoute>,
+}
+
+impl MockState {
+    /// One time responses are used first. Then the most recently registered response
+    fn take_response(&mut self, method: &str, path: &str) -> Option<MockResponse> {
+        let matches = |route: &MockRoute| route.method == method && normalize(&route.path) == path;
+        if let Some(index) = self
+            .routes
+            .iter()
+            .position(|route| route.once && matches(route))
+        {
+            return Some(self.routes.remove(index).response);
+        }
+        self.routes
+            .iter()
+            .rev()
+            .find(|route| matches(route))
+            .map(|route| route.response.clone())
+    }
+}
+
+/// An in-process HTTP stand-in for Reddit.
+///
+/// Must be started from within a Tokio Runtime. The server stops when this is dropped
+pub struct MockReddit {
+    address: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl Debug for MockReddit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[MockReddit] Listening on {}", self.address)
+    }
+}
+
+impl MockReddit {
+    /// Binds a random local port and starts serving
+    pub fn start() -> std::io::Result<MockReddit> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+        });
+        let (shutdown, receiver) = oneshot::channel::<()>();
+        let server = Server::from_tcp(listener)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::Other, error))?
+            .serve(make_service)
+            .with_graceful_shutdown(async {
+                receiver.await.ok();
+            });
+        tokio::spawn(server);
+        trace!("MockReddit listening on {}", address);
+        Ok(MockReddit {
+            address,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+    /// The base URL. Example: `http://127.0.0.1:8080`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+    /// Endpoints pointing at this server
+    pub fn endpoints(&self) -> Endpoints {
+        Endpoints::with_base(self.url())
+    }
+    /// A ClientBuilder pointing at this server
+    pub fn client_builder<S: Into<String>>(&self, user_agent: S) -> ClientBuilder {
+        ClientBuilder::new(user_agent).endpoints(self.endpoints())
+    }
+    /// A Client logged in as `rraw_test` with a [PasswordAuthenticator]
+    pub async fn password_client(&self) -> Result<Client<PasswordAuthenticator>, Error> {
+        self.password_client_with(|builder| builder).await
+    }
+    /// Same as [MockReddit::password_client]. `configure` sets the options of the ClientBuilder
+    pub async fn password_client_with<F>(
+        &self,
+        configure: F,
+    ) -> Result<Client<PasswordAuthenticator>, Error>
+    where
+        F: FnOnce(ClientBuilder) -> ClientBuilder,
+    {
+        let auth =
+            PasswordAuthenticator::new("client_id", "client_secret", "rraw_test", "password");
+        configure(self.client_builder(USER_AGENT)).login(auth).await
+    }
+    /// Every request received so far. In order
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+    /// Every request received so far to the path. A trailing `/` or `.json` is ignored
+    pub fn requests_to(&self, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| normalize(&request.path) == normalize(path))
+            .collect()
+    }
+    /// Forgets the recorded requests
+    pub fn clear_requests(&self) {
+        self.state.lock().unwrap().requests.clear();
+    }
+    /// Serves the response for every request with the method and path. Replaces the fixture
+    pub fn respond(&self, method: &str, path: &str, response: MockResponse) {
+        self.add_route(method, path, response, false);
+    }
+    /// Serves the response for the next request with the method and path.
+    pub fn respond_once(&self, method: &str, path: &str, response: MockResponse) {
+        self.add_route(method, path, response, true);
+    }
+    fn add_route(&self, method: &str, path: &str, response: MockResponse, once: bool) {
+        self.state.lock().unwrap().routes.push(MockRoute {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            response,
+            once,
+        });
+    }
+}
+
+impl Drop for MockReddit {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<MockState>>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+    let recorded = RecordedRequest {
+        method: parts.method.to_string(),
+        path: parts.uri.path().to_string(),
+        query: parts.uri.query().map(ToString::to_string),
+        headers: parts
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    value.to_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect(),
+        body: String::from_utf8_lossy(&body).to_string(),
+    };
+    trace!("MockReddit {} {}", recorded.method, recorded.path);
+    let response = {
+        let mut state = state.lock().unwrap();
+        let response = state
+            .take_response(&recorded.method, normalize(&recorded.path))
+            .unwrap_or_else(|| fixture_response(&recorded.method, &recorded.path));
+        state.requests.push(recorded);
+        response
+    };
+    let mut builder = Response::builder().status(response.status);
+    for (name, value) in response.headers {
+        builder = builder.header(name, value);
+    }
+    Ok(builder.body(Body::from(response.body)).unwrap())
+}
+
+/// Routes match with or without a trailing `/` or `.json`
+fn normalize(path: &str) -> &str {
+    path.trim_end_matches('/').trim_end_matches(".json")
+}
+
+/// The default routes
+fn fixture_response(method: &str, path: &str) -> MockResponse {
+    let path = normalize(path);
+    let segments: Vec<&str> = path.split('/').skip(1).collect();
+    let body = match (method, segments.as_slice()) {
+        ("POST", ["api", "v1", "access_token"]) => fixtures::access_token(),
+        ("POST", ["api", "v1", "revoke_token"]) => return MockResponse::new(200, ""),
+        ("POST", ["api", "compose"]) => fixtures::api_json(json!({})),
+        ("POST", ["api", "read_message" | "unread_message"])
+        | ("POST", ["api", "vote" | "save" | "unsave" | "hide" | "unhide"]) => json!({}),
+        ("POST", ["api", "comment"]) => fixtures::api_json(json!({
+            "things": [fixtures::comment("mockreply", "rust")]
+        })),
+        ("POST", ["api", "editusertext"]) => fixtures::api_json(json!({
+            "things": [fixtures::comment("mockc1", "rust")]
+        })),
+        ("POST", ["api", "del"]) => json!({}),
+        ("GET", ["api", "morechildren"]) => fixtures::api_json(json!({ "things": [] })),
+        ("POST", ["api", "report"]) => json!({ "json": { "errors": [] } }),
+        ("POST", ["api", "submit"]) => fixtures::api_json(json!({
+            "url": "https://www.reddit.com/r/rust/comments/mocknew/mock_submission/",
+            "drafts_count": 0,
+            "id": "mocknew",
+            "name": "t3_mocknew",
+        })),
+        ("POST", ["api", "friend" | "unfriend" | "block"])
+        | ("POST", ["r", _, "api", "friend" | "unfriend"]) => json!({"success": true}),
+        ("GET", ["api", "v1", "me"]) => fixtures::me("rraw_test"),
+        ("GET", ["u" | "user", name, "about"]) => fixtures::account(name),
+        ("GET", ["user", _, "comments"]) => fixtures::listing(
+            vec![
+                fixtures::comment("mockc1", "rust"),
+                fixtures::comment("mockc2", "rust"),
+            ],
+            None,
+        ),
+        ("GET", ["user", _, "submitted"]) => fixtures::listing(
+            vec![
+                fixtures::link("mock01", "rust"),
+                fixtures::link("mock02", "rust"),
+            ],
+            None,
+        ),
+        ("GET", ["user", _, _]) => fixtures::listing(
+            vec![
+                fixtures::link("mock01", "rust"),
+                fixtures::comment("mockc1", "rust"),
+            ],
+            None,
+        ),
+        ("GET", ["r", name, "about"]) => fixtures::subreddit(name),
+        (
+            "GET",
+            ["r", name, "about", "modqueue" | "reports" | "spam" | "edited" | "unmoderated"],
+        ) => fixtures::listing(
+            vec![
+                fixtures::reported(fixtures::link("mock01", name)),
+                fixtures::reported(fixtures::comment("mockc1", name)),
+            ],
+            None,
+        ),
+        ("GET", ["r", name, "comments"]) => fixtures::listing(
+            vec![
+                fixtures::comment("mockc2", name),
+                fixtures::comment("mockc1", name),
+            ],
+            None,
+        ),
+        ("GET", ["r", name, "comments", id, ..]) => json!([
+            fixtures::listing(vec![fixtures::link(id, name)], None),
+            fixtures::listing(vec![fixtures::comment("mockc1", name)], None),
+        ]),
+        ("GET", ["r", name, _]) | ("GET", ["domain", name]) => fixtures::listing(
+            vec![
+                fixtures::link("mock01", name),
+                fixtures::link("mock02", name),
+            ],
+            None,
+        ),
+        ("GET", ["message", _]) => fixtures::listing(
+            vec![fixtures::message("mockm1"), fixtures::message("mockm2")],
+            None,
+        ),
+        ("GET", ["subreddits", "search"]) => fixtures::listing(
+            vec![fixtures::subreddit("rust"), fixtures::subreddit("rustjerk")],
+            None,
+        ),
+        ("GET", ["users", "search"]) => fixtures::listing(
+            vec![
+                fixtures::account("KingTuxWH"),
+                fixtures::account("TheSmartKing"),
+            ],
+            None,
+        ),
+        _ => return MockResponse::not_found(),
+    };
+    MockResponse::json(&body)
+}
@@ -1,10 +1,12 @@
 use crate::auth::Authorized;
 use crate::comments::response::CommentsResponse;
 use crate::error::Error;
-use crate::message::response::MessageListing;
+use crate::message::response::{Message, MessageListing};
 use crate::message::WhereMessage;
-use crate::responses::FullName;
+use crate::responses::{FullName, GenericResponse, HasFullName, RedditResponse};
+use crate::stream::{poll_new_with, StreamOptions};
 use crate::Client;
+use futures_util::Stream;
 use serde_json::Value;
 
 use crate::responses::listing::RedditListing;
@@ -13,6 +15,7 @@ use crate::subreddit::response::Friend;
 use crate::user::response::MeResponse;
 
 use crate::utils::options::FeedOption;
+use crate::utils::request::ApiRequest;
 
 /// The User Object for Reddit
 pub struct Me<'a, A: Authorized> {
@@ -23,9 +26,9 @@ pub struct Me<'a, A: Authorized> {
 impl<'a, A: Authorized> Me<'a, A> {
     /// For blocking the author of a thing via inbox. - Reddit API
     pub async fn block_author(&self, full_name: FullName) -> Result<Friend, Error> {
-        let body = reqwest::Body::from(format!("id={}", full_name));
+        let request = ApiRequest::new("/api/block").form("id", full_name);
         self.client
-            .post_json::<Friend>("/api/block", true, body)
+            .post_json::<Friend>(&request.url(), true, request.body())
             .await
     }
     /// Gets the Messages. Default for where_message is Inbox
@@ -34,14 +37,85 @@ impl<'a, A: Authorized> Me<'a, A> {
         where_message: Option<WhereMessage>,
         feed: Option<FeedOption>,
     ) -> Result<MessageListing, Error> {
-        let mut string = format!("/message/{}", where_message.unwrap_or(WhereMessage::Inbox));
-        if let Some(f) = feed {
-            f.extend(&mut string);
-        }
+        let request = ApiRequest::new(format!(
+            "/message/{}",
+            where_message.unwrap_or(WhereMessage::Inbox)
+        ))
+        .feed(feed);
         self.client
-            .get_json::<MessageListing>(&string, true, false)
+            .get_json::<MessageListing>(&request.url(), true, false)
             .await
     }
+    /// Marks the messages as read
+    pub async fn mark_read(&self, full_names: &[FullName]) -> Result<(), Error> {
+        self.message_action("/api/read_message", full_names).await
+    }
+    /// Marks the messages as unread
+    pub async fn mark_unread(&self, full_names: &[FullName]) -> Result<(), Error> {
+        self.message_action("/api/unread_message", full_names).await
+    }
+    async fn message_action(&self, path: &str, full_names: &[FullName]) -> Result<(), Error> {
+        let ids = full_names
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let request = ApiRequest::new(path).form("id", ids);
+        self.client
+            .post_empty(&request.url(), true, request.body())
+            .await
+    }
+    /// Streams unread messages as they arrive. Each message is yielded once.
+    ///
+    /// If `mark_read` is true the new messages of each poll are marked read with one request before
+    /// they are yielded. If that fails the error is yielded and they are tried again on the next poll.
+    /// See [poll_new_with](crate::stream::poll_new_with)
+    /// ```no_run
+    /// #[tokio::main]
+    /// async fn main() ->anyhow::Result<()>{
+    ///    use std::env;
+    ///    use futures_util::StreamExt;
+    ///    use rraw::auth::PasswordAuthenticator;
+    ///    use rraw::Client;
+    ///    let client = Client:: login(PasswordAuthenticator::new(env::var("CLIENT_ID")?,env::var("CLIENT_SECRET")?,env::var("USERNAME")?,env::var("PASSWORD")?), "RRAW Test (by u/KingTuxWH)").await?;
+    ///    let me = client.me().await?;
+    ///    let mut inbox = Box::pin(me.stream_inbox(None, true));
+    ///    while let Some(message) = inbox.next().await {
+    ///        println!("{:?}", message?.data.body);
+    ///    }
+    ///    Ok(())
+    /// }
+    /// ```
+    pub fn stream_inbox(
+        &self,
+        options: Option<StreamOptions>,
+        mark_read: bool,
+    ) -> impl Stream<Item = Result<GenericResponse<Message>, Error>> + '_ {
+        let path = format!("/message/{}", WhereMessage::Unread);
+        poll_new_with(
+            options.unwrap_or_default(),
+            move |feed| {
+                let path = ApiRequest::new(path.clone()).feed(Some(feed)).url();
+                async move {
+                    self.client
+                        .get_json::<MessageListing>(&path, true, false)
+                        .await
+                }
+            },
+            move |full_names| async move {
+                if mark_read {
+                    self.mark_read(&full_names).await?;
+                }
+                Ok(())
+            },
+        )
+    }
+    /// Replies to a message in the inbox.
+    ///
+    /// Returns a Message for private messages and a Comment for comment replies
+    pub async fn reply(&self, message: &Message, text: &str) -> Result<RedditResponse, Error> {
+        self.client.reply(message.full_name(), text).await
+    }
     /// Composes a message.
     pub async fn compose(
         &self,
@@ -49,22 +123,21 @@ impl<'a, A: Authorized> Me<'a, A> {
         subject: String,
         body: String,
     ) -> Result<Value, Error> {
-        let string = format!("api_type=json&subject={subject}&text={body}&to={recipient}");
-        let body = reqwest::Body::from(string);
+        let request = ApiRequest::new("/api/compose")
+            .form("api_type", "json")
+            .form("subject", subject)
+            .form("text", body)
+            .form("to", recipient);
         self.client
-            .post_json::<Value>("/api/compose", true, body)
+            .post_api_raw(&request.url(), request.body())
             .await
     }
 
     /// Comments
     pub async fn comments(&self, feed: Option<FeedOption>) -> Result<CommentsResponse, Error> {
-        let mut string = format!("/user/{}/comments", &self.me.about.name);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request = ApiRequest::new(format!("/user/{}/comments", &self.me.about.name)).feed(feed);
         self.client
-            .get_json::<CommentsResponse>(&*string, false, false)
+            .get_json::<CommentsResponse>(&request.url(), false, false)
             .await
     }
     /// user Submissions
@@ -72,55 +145,37 @@ impl<'a, A: Authorized> Me<'a, A> {
         &self,
         feed: Option<FeedOption>,
     ) -> Result<SubmissionsResponse, Error> {
-        let mut string = format!("/user/{}/submitted", &self.me.about.name);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request =
+            ApiRequest::new(format!("/user/{}/submitted", &self.me.about.name)).feed(feed);
         self.client
-            .get_json::<SubmissionsResponse>(&*string, false, false)
+            .get_json::<SubmissionsResponse>(&request.url(), false, false)
             .await
     }
 
     /// User Overview
     pub async fn overview(&self, feed: Option<FeedOption>) -> Result<RedditListing, Error> {
-        let mut string = format!("/user/{}/overview", &self.me.about.name);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request = ApiRequest::new(format!("/user/{}/overview", &self.me.about.name)).feed(feed);
         self.client
-            .get_json::<RedditListing>(&*string, false, false)
+            .get_json::<RedditListing>(&request.url(), false, false)
             .await
     }
     pub async fn saved(&self, feed: Option<FeedOption>) -> Result<RedditListing, Error> {
-        let mut string = format!("/user/{}/saved", &self.me.about.name);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request = ApiRequest::new(format!("/user/{}/saved", &self.me.about.name)).feed(feed);
         self.client
-            .get_json::<RedditListing>(&*string, false, false)
+            .get_json::<RedditListing>(&request.url(), false, false)
             .await
     }
     pub async fn up_voted(&self, feed: Option<FeedOption>) -> Result<RedditListing, Error> {
-        let mut string = format!("/user/{}/upvoted", &self.me.about.name);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request = ApiRequest::new(format!("/user/{}/upvoted", &self.me.about.name)).feed(feed);
         self.client
-            .get_json::<RedditListing>(&string, false, false)
+            .get_json::<RedditListing>(&request.url(), false, false)
             .await
     }
     pub async fn down_voted(&self, feed: Option<FeedOption>) -> Result<RedditListing, Error> {
-        let mut string = format!("/user/{}/downvoted", &self.me.about.name);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request =
+            ApiRequest::new(format!("/user/{}/downvoted", &self.me.about.name)).feed(feed);
         self.client
-            .get_json::<RedditListing>(&string, false, false)
+            .get_json::<RedditListing>(&request.url(), false, false)
             .await
     }
 }
@@ -11,6 +11,7 @@ use crate::submission::response::SubmissionsResponse;
 use crate::user::response::AboutUser;
 
 use crate::utils::options::FeedOption;
+use crate::utils::request::ApiRequest;
 
 /// The User Object for Reddit
 pub struct User<'a, A: Authenticator> {
@@ -27,14 +28,10 @@ impl<'a, A: Authenticator> PartialEq for User<'a, A> {
 impl<'a, A: Authenticator> User<'a, A> {
     /// Comments
     pub async fn comments(&self, feed: Option<FeedOption>) -> Result<CommentsResponse, Error> {
-        let mut string = format!("/user/{}/comments.json", &self.user);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request = ApiRequest::new(format!("/user/{}/comments.json", &self.user)).feed(feed);
         return self
             .me
-            .get_json::<CommentsResponse>(&*string, false, false)
+            .get_json::<CommentsResponse>(&request.url(), false, false)
             .await;
     }
     /// user Submissions
@@ -42,26 +39,18 @@ impl<'a, A: Authenticator> User<'a, A> {
         &self,
         feed: Option<FeedOption>,
     ) -> Result<SubmissionsResponse, Error> {
-        let mut string = format!("/user/{}/submitted.json", &self.user);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request = ApiRequest::new(format!("/user/{}/submitted.json", &self.user)).feed(feed);
         return self
             .me
-            .get_json::<SubmissionsResponse>(&*string, false, false)
+            .get_json::<SubmissionsResponse>(&request.url(), false, false)
             .await;
     }
     /// User Overview
     pub async fn overview(&self, feed: Option<FeedOption>) -> Result<RedditListing, Error> {
-        let mut string = format!("/user/{}/overview.json", &self.user);
-        if let Some(options) = feed {
-            string.push('?');
-            string.push_str(options.url().as_str());
-        }
+        let request = ApiRequest::new(format!("/user/{}/overview.json", &self.user)).feed(feed);
         return self
             .me
-            .get_json::<RedditListing>(&*string, false, false)
+            .get_json::<RedditListing>(&request.url(), false, false)
             .await;
     }
 }
@@ -1,14 +1,64 @@
+#[cfg(not(feature = "tokio"))]
+use std::future::Future;
+#[cfg(not(feature = "tokio"))]
+use std::pin::Pin;
+#[cfg(not(feature = "tokio"))]
+use std::sync::{Arc, Mutex};
+#[cfg(not(feature = "tokio"))]
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
 use base64::Engine;
 
 pub mod options;
+pub mod request;
+
+/// Waits for the duration. Uses the tokio timer when the `tokio` dependency is enabled
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(feature = "tokio")]
+    tokio::time::sleep(duration).await;
+    #[cfg(not(feature = "tokio"))]
+    ThreadSleep::new(duration).await;
+}
+
+/// A timer that does not need a runtime. A thread wakes the task once the duration has passed
+#[cfg(not(feature = "tokio"))]
+struct ThreadSleep {
+    /// If the duration has passed and the waker of the task
+    state: Arc<Mutex<(bool, Option<Waker>)>>,
+}
+
+#[cfg(not(feature = "tokio"))]
+impl ThreadSleep {
+    fn new(duration: Duration) -> ThreadSleep {
+        let state = Arc::new(Mutex::new((false, None::<Waker>)));
+        let timer = state.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            let mut timer = timer.lock().unwrap();
+            timer.0 = true;
+            if let Some(waker) = timer.1.take() {
+                waker.wake();
+            }
+        });
+        ThreadSleep { state }
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+impl Future for ThreadSleep {
+    type Output = ();
 
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.0 {
+            return Poll::Ready(());
+        }
+        state.1 = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
 
-pub fn basic_header(username: &str, password: &str)->String{
-    base64::engine::general_purpose::STANDARD.encode(
-        format!(
-            "{}:{}",
-            username,
-            password
-        )
-    )
-}
\ No newline at end of file
+pub fn basic_header(username: &str, password: &str) -> String {
+    base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password))
+}
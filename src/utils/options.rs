@@ -2,6 +2,9 @@ use core::fmt;
 use std::fmt::{Display, Formatter};
 
 pub use serde::Serialize;
+
+use crate::utils::request::Params;
+
 #[derive(Clone, Debug, Serialize)]
 pub struct CommentOption {
     pub sort: Option<String>,
@@ -10,28 +13,27 @@ pub struct CommentOption {
 }
 
 impl CommentOption {
-    ///Returns the URL extension for the request
+    /// The query parameters for the request
+    pub fn params(&self) -> Params {
+        Params::new()
+            .with_opt("sort", self.sort.as_ref())
+            .with_opt("depth", self.depth)
+            .with_opt("limit", self.limit)
+    }
+    ///Returns the URL extension for the request. Percent encoded
     pub fn url(&self) -> String {
-        let mut url = String::new();
-        if let Some(sort) = &self.sort {
-            url.push_str(&format!("&sort={sort}"));
-        }
-        if let Some(depth) = &self.depth {
-            url.push_str(&format!("&depth={depth}"));
-        }
-
-        if let Some(limit) = &self.limit {
-            url.push_str(&format!("&limit={limit}"));
-        }
-        url
+        self.params().encode()
     }
     pub fn extend(&self, value: &mut String) {
-        value.push('?');
-        value.push_str(self.url().as_str());
+        let params = self.params();
+        if !params.is_empty() {
+            value.push('?');
+            value.push_str(&params.encode());
+        }
     }
 }
 ///A simple object to let you set informationons about the listing you are getting
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct FeedOption {
     pub after: Option<String>,
     pub before: Option<String>,
@@ -41,31 +43,25 @@ pub struct FeedOption {
 }
 
 impl FeedOption {
-    ///Returns the URL extension for the request
+    /// The query parameters for the request
+    pub fn params(&self) -> Params {
+        Params::new()
+            .with_opt("after", self.after.as_ref())
+            .with_opt("before", self.before.as_ref())
+            .with_opt("count", self.count)
+            .with_opt("t", self.period)
+            .with_opt("limit", self.limit)
+    }
+    ///Returns the URL extension for the request. Percent encoded
     pub fn url(&self) -> String {
-        let mut url = String::new();
-        if let Some(after) = &self.after {
-            url.push_str(&format!("&after={after}"));
-        }
-        if let Some(before) = &self.before {
-            url.push_str(&format!("&before={before}"));
-        }
-
-        if let Some(count) = &self.count {
-            url.push_str(&format!("&count={count}"));
-        }
-
-        if let Some(period) = &self.period {
-            url.push_str(&format!("&t={period}"));
-        }
-        if let Some(limit) = &self.limit {
-            url.push_str(&format!("&limit={limit}"));
-        }
-        url
+        self.params().encode()
     }
     pub fn extend(&self, value: &mut String) {
-        value.push('?');
-        value.push_str(self.url().as_str());
+        let params = self.params();
+        if !params.is_empty() {
+            value.push('?');
+            value.push_str(&params.encode());
+        }
     }
 }
 
@@ -108,6 +104,24 @@ impl Display for TimePeriod {
     }
 }
 
+/// The `only` filter of the moderation queues
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ModQueueFilter {
+    /// Submissions
+    Links,
+    Comments,
+}
+
+impl Display for ModQueueFilter {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let string = match self {
+            ModQueueFilter::Links => "links",
+            ModQueueFilter::Comments => "comments",
+        };
+        write!(f, "{}", string)
+    }
+}
+
 /// FriendType
 pub enum FriendType {
     /// Contributor
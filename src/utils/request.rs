@@ -0,0 +1,137 @@
+use std::fmt::Display;
+
+use reqwest::Body;
+
+use crate::utils::options::FeedOption;
+
+/// Parameters that are form encoded. Used for query strings and request bodies
+/// ```rust
+/// use rraw::utils::request::Params;
+/// let params = Params::new()
+///     .with("subject", "Fish & Chips")
+///     .with_opt("limit", Some(5))
+///     .with_opt::<u32>("count", None);
+/// assert_eq!(params.encode(), "subject=Fish+%26+Chips&limit=5");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// No parameters
+    pub fn new() -> Params {
+        Params::default()
+    }
+    /// Adds the parameter
+    pub fn push<K: Into<String>, V: Display>(&mut self, key: K, value: V) -> &mut Params {
+        self.0.push((key.into(), value.to_string()));
+        self
+    }
+    /// Adds the parameter
+    pub fn with<K: Into<String>, V: Display>(mut self, key: K, value: V) -> Params {
+        self.push(key, value);
+        self
+    }
+    /// Adds the parameter if there is a value
+    pub fn with_opt<V: Display>(mut self, key: &str, value: Option<V>) -> Params {
+        if let Some(value) = value {
+            self.push(key, value);
+        }
+        self
+    }
+    /// Adds all the parameters of the other
+    pub fn extend(&mut self, other: Params) {
+        self.0.extend(other.0);
+    }
+    /// If there are no parameters
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The value of the first parameter with the key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+    /// `application/x-www-form-urlencoded`. Also valid as a query string
+    pub fn encode(&self) -> String {
+        form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&self.0)
+            .finish()
+    }
+}
+
+impl From<Params> for Body {
+    fn from(params: Params) -> Self {
+        Body::from(params.encode())
+    }
+}
+
+/// A path with a percent encoded query and a form encoded body
+/// ```rust
+/// use rraw::utils::request::ApiRequest;
+/// let request = ApiRequest::new("/subreddits/search")
+///     .query("q", "rust lang")
+///     .form("text", "a=b");
+/// assert_eq!(request.url(), "/subreddits/search?q=rust+lang");
+/// assert_eq!(request.form_params().encode(), "text=a%3Db");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ApiRequest {
+    path: String,
+    query: Params,
+    form: Params,
+}
+
+impl ApiRequest {
+    /// A request to the path. The path is not encoded
+    pub fn new<S: Into<String>>(path: S) -> ApiRequest {
+        ApiRequest {
+            path: path.into(),
+            ..ApiRequest::default()
+        }
+    }
+    /// Adds a query parameter
+    pub fn query<K: Into<String>, V: Display>(mut self, key: K, value: V) -> ApiRequest {
+        self.query.push(key, value);
+        self
+    }
+    /// Adds a query parameter if there is a value
+    pub fn query_opt<V: Display>(mut self, key: &str, value: Option<V>) -> ApiRequest {
+        self.query = self.query.with_opt(key, value);
+        self
+    }
+    /// Adds the parameters of the FeedOption to the query
+    pub fn feed(mut self, feed: Option<FeedOption>) -> ApiRequest {
+        if let Some(feed) = feed {
+            self.query.extend(feed.params());
+        }
+        self
+    }
+    /// Adds a form parameter to the body
+    pub fn form<K: Into<String>, V: Display>(mut self, key: K, value: V) -> ApiRequest {
+        self.form.push(key, value);
+        self
+    }
+    /// Adds a form parameter to the body if there is a value
+    pub fn form_opt<V: Display>(mut self, key: &str, value: Option<V>) -> ApiRequest {
+        self.form = self.form.with_opt(key, value);
+        self
+    }
+    /// The path and the query
+    pub fn url(&self) -> String {
+        if self.query.is_empty() {
+            self.path.clone()
+        } else {
+            format!("{}?{}", self.path, self.query.encode())
+        }
+    }
+    /// The form parameters
+    pub fn form_params(&self) -> &Params {
+        &self.form
+    }
+    /// The form encoded body
+    pub fn body(&self) -> Body {
+        Body::from(self.form.encode())
+    }
+}
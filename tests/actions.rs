@@ -0,0 +1,188 @@
+use std::str::FromStr;
+
+use log::LevelFilter;
+use rraw::actions::{Actionable, VoteDirection};
+use rraw::comments::CommentType;
+use rraw::responses::{FullName, RedditTypeResponse};
+use rraw::submission::{SubmissionRetriever, SubmissionType};
+use rraw::testing::{fixtures, MockReddit, MockResponse};
+use serde_json::json;
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+#[tokio::test]
+async fn submission_actions() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let subreddit = client.subreddit("rust").await?;
+    let hot = subreddit.hot(None).await?;
+    let submission = hot.data.children[0].data.to_submission(&client);
+
+    submission.upvote().await?;
+    submission.downvote().await?;
+    submission.clear_vote().await?;
+    submission.save(Some("rust")).await?;
+    submission.unsave().await?;
+    submission.hide().await?;
+    submission.unhide().await?;
+
+    let votes: Vec<String> = reddit
+        .requests_to("/api/vote")
+        .iter()
+        .map(|request| request.form()["dir"].clone())
+        .collect();
+    assert_eq!(votes, ["1", "-1", "0"]);
+    let save = reddit.requests_to("/api/save")[0].form();
+    assert_eq!(save["id"], "t3_mock01");
+    assert_eq!(save["category"], "rust");
+    assert_eq!(
+        reddit.requests_to("/api/unsave")[0].form()["id"],
+        "t3_mock01"
+    );
+    assert_eq!(reddit.requests_to("/api/hide")[0].form()["id"], "t3_mock01");
+    assert_eq!(
+        reddit.requests_to("/api/unhide")[0].form()["id"],
+        "t3_mock01"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn comment_report() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let me = client.me().await?;
+    let comments = me.comments(None).await?;
+    let comment = comments.data.children[0].data.to_comment(&client);
+
+    comment.report("Spam & Scam").await?;
+
+    let report = reddit.requests_to("/api/report")[0].form();
+    assert_eq!(report["thing_id"], "t1_mockc1");
+    assert_eq!(report["reason"], "Spam & Scam");
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_name_actions() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    reddit.respond_once(
+        "POST",
+        "/api/report",
+        MockResponse::new(
+            200,
+            r#"{"json": {"errors": [["SUBREDDIT_NOEXIST", "that subreddit doesn't exist", "sr"]]}}"#,
+        ),
+    );
+
+    client
+        .vote(FullName::from_str("t1_abc")?, VoteDirection::Up)
+        .await?;
+    assert!(client
+        .report(FullName::from_str("t1_abc")?, "Spam")
+        .await
+        .is_err());
+    let hidden = [FullName::from_str("t3_a")?, FullName::from_str("t3_b")?];
+    client.hide(&hidden).await?;
+    assert_eq!(reddit.requests_to("/api/hide")[0].form()["id"], "t3_a,t3_b");
+    Ok(())
+}
+
+#[tokio::test]
+async fn reply_edit_delete() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let subreddit = client.subreddit("rust").await?;
+    let hot = subreddit.hot(None).await?;
+    let submission = hot.data.children[0].data.to_submission(&client);
+
+    let reply = submission.reply("Nice post").await?;
+    assert_eq!(reply.id, "mockreply");
+    let form = reddit.requests_to("/api/comment")[0].form();
+    assert_eq!(form["thing_id"], "t3_mock01");
+    assert_eq!(form["text"], "Nice post");
+    assert_eq!(form["api_type"], "json");
+
+    reddit.respond_once(
+        "POST",
+        "/api/editusertext",
+        MockResponse::json(&fixtures::api_json(json!({
+            "things": [fixtures::link("mock01", "rust")]
+        }))),
+    );
+    let edited = submission.edit("Updated text").await?;
+    assert_eq!(edited.id, "mock01");
+    let form = reddit.requests_to("/api/editusertext")[0].form();
+    assert_eq!(form["thing_id"], "t3_mock01");
+    assert_eq!(form["text"], "Updated text");
+
+    submission.delete().await?;
+    assert_eq!(reddit.requests_to("/api/del")[0].form()["id"], "t3_mock01");
+    Ok(())
+}
+
+#[tokio::test]
+async fn comment_reply_and_edit() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let me = client.me().await?;
+    let comments = me.comments(None).await?;
+    let comment = comments.data.children[0].data.to_comment(&client);
+
+    comment.reply("Agreed").await?;
+    assert_eq!(
+        reddit.requests_to("/api/comment")[0].form()["thing_id"],
+        "t1_mockc1"
+    );
+    let edited = comment.edit("Edited").await?;
+    assert_eq!(edited.id, "mockc1");
+
+    reddit.respond_once(
+        "POST",
+        "/api/comment",
+        MockResponse::new(
+            200,
+            r#"{"json": {"errors": [["TOO_LONG", "this is too long", "text"]]}}"#,
+        ),
+    );
+    assert!(comment.reply("Too long").await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn message_reply() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let me = client.me().await?;
+    let inbox = me.get_messages(None, None).await?;
+    reddit.respond_once(
+        "POST",
+        "/api/comment",
+        MockResponse::json(&fixtures::api_json(json!({
+            "things": [fixtures::message("mockm3")]
+        }))),
+    );
+
+    let reply = me.reply(&inbox.data.children[0].data, "Thanks").await?;
+    assert!(matches!(reply.data, RedditTypeResponse::Message(_)));
+    assert_eq!(
+        reddit.requests_to("/api/comment")[0].form()["thing_id"],
+        "t4_mockm1"
+    );
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use log::LevelFilter;
+use serde_json::json;
+
+use rraw::auth::{ClientCredentialsAuthenticator, INSTALLED_CLIENT_GRANT};
+use rraw::testing::{fixtures, MockReddit, MockResponse};
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+#[tokio::test]
+async fn client_credentials() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let auth = ClientCredentialsAuthenticator::new("client_id", "client_secret");
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth)
+        .await?;
+    assert!(client.oauth);
+    let login = &reddit.requests_to("/api/v1/access_token")[0];
+    assert_eq!(login.form()["grant_type"], "client_credentials");
+    assert_eq!(
+        login.header("authorization"),
+        Some("Basic Y2xpZW50X2lkOmNsaWVudF9zZWNyZXQ=")
+    );
+
+    client.subreddit("rust").await?;
+    let about = &reddit.requests_to("/r/rust/about")[0];
+    assert_eq!(
+        about.header("authorization"),
+        Some("Bearer mock_access_token")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn installed_client_refreshes() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let mut expired = fixtures::access_token();
+    expired["expires_in"] = json!(0);
+    reddit.respond_once("POST", "/api/v1/access_token", MockResponse::json(&expired));
+
+    let auth = ClientCredentialsAuthenticator::installed("client_id", "DO_NOT_TRACK_THIS_DEVICE");
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth)
+        .await?;
+    let login = &reddit.requests_to("/api/v1/access_token")[0];
+    assert_eq!(login.form()["grant_type"], INSTALLED_CLIENT_GRANT);
+    assert_eq!(login.form()["device_id"], "DO_NOT_TRACK_THIS_DEVICE");
+    assert_eq!(
+        login.header("authorization"),
+        Some("Basic Y2xpZW50X2lkOg==")
+    );
+
+    // The first token expired immediately so the request logs in again
+    client.subreddit("rust").await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 2);
+    client.subreddit("rust").await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 2);
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use log::LevelFilter;
+
+use rraw::auth::LocalCodeFlow;
+use rraw::builder::Endpoints;
+use rraw::error::Error;
+use rraw::testing::MockReddit;
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+async fn bind() -> anyhow::Result<LocalCodeFlow> {
+    Ok(LocalCodeFlow::bind("client_id", "client_secret", "http://127.0.0.1:0/callback").await?)
+}
+
+/// Plays the browser being redirected back by Reddit
+async fn redirect(url: String) -> anyhow::Result<reqwest::StatusCode> {
+    Ok(reqwest::get(url).await?.status())
+}
+
+#[tokio::test]
+async fn receives_code() -> anyhow::Result<()> {
+    init();
+    let flow = bind()
+        .await?
+        .duration("permanent")
+        .scope(vec!["identity", "read"])
+        .endpoints(Endpoints::with_base("http://reddit.test"));
+    let redirect_uri = flow.redirect_uri().to_string();
+    assert!(redirect_uri.starts_with("http://127.0.0.1:"));
+    assert!(!redirect_uri.contains(":0/"));
+    assert_eq!(flow.state().len(), 32);
+
+    let url = flow.authorization_url();
+    assert!(url.starts_with("http://reddit.test/api/v1/authorize?"));
+    assert!(url.contains(&format!("state={}", flow.state())));
+    assert!(url.contains("duration=permanent"));
+    assert!(url.contains("scope=identity,read"));
+
+    let callback = format!("{}?state={}&code=the_code", redirect_uri, flow.state());
+    let favicon = redirect_uri.replace("/callback", "/favicon.ico");
+    let browser = async {
+        assert_eq!(redirect(favicon).await?, 404);
+        redirect(callback).await
+    };
+    let (auth, status) = tokio::join!(flow.wait(), browser);
+    assert_eq!(status?, 200);
+
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth?)
+        .await?;
+    let login = &reddit.requests_to("/api/v1/access_token")[0];
+    assert_eq!(login.form()["code"], "the_code");
+    assert_eq!(login.form()["redirect_uri"], redirect_uri);
+    assert_eq!(
+        client.refresh_token().as_deref(),
+        Some("mock_refresh_token")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejects_bad_redirects() -> anyhow::Result<()> {
+    init();
+    let flow = bind().await?;
+    let callback = format!("{}?state=forged&code=the_code", flow.redirect_uri());
+    let (auth, status) = tokio::join!(flow.wait(), redirect(callback));
+    assert_eq!(status?, 400);
+    assert!(matches!(auth.unwrap_err(), Error::StateMismatch));
+
+    let flow = bind().await?;
+    let callback = format!(
+        "{}?state={}&error=access_denied",
+        flow.redirect_uri(),
+        flow.state()
+    );
+    let (auth, status) = tokio::join!(flow.wait(), redirect(callback));
+    assert_eq!(status?, 200);
+    match auth.unwrap_err() {
+        Error::AuthorizationFailed(error) => assert_eq!(error, "access_denied"),
+        error => panic!("Unexpected Error {}", error),
+    }
+
+    let error = LocalCodeFlow::bind("client_id", "client_secret", "https://example.com/callback")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::Other(_)));
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use log::LevelFilter;
+use rraw::auth::AnonymousAuthenticator;
+use rraw::comments::response::CommentResponse;
+use rraw::comments::tree::{CommentNodeKind, CommentTree, MoreChildren};
+use rraw::comments::CommentRetriever;
+use rraw::error::Error;
+use rraw::responses::listing::ListingArray;
+use rraw::responses::GenericResponse;
+use rraw::submission::{SubmissionRetriever, SubmissionType};
+use rraw::testing::{fixtures, MockReddit, MockResponse};
+use rraw::utils::options::CommentOption;
+use serde_json::{json, Value};
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+/// A comment with the replies and parent
+fn comment(id: &str, parent_id: &str, replies: Vec<Value>) -> Value {
+    let mut comment = fixtures::comment(id, "rust");
+    comment["data"]["parent_id"] = json!(parent_id);
+    if !replies.is_empty() {
+        comment["data"]["replies"] = fixtures::listing(replies, None);
+    }
+    comment
+}
+
+fn comments_response() -> Value {
+    json!([
+        fixtures::listing(vec![fixtures::link("mock01", "rust")], None),
+        fixtures::listing(
+            vec![
+                comment(
+                    "a",
+                    "t3_mock01",
+                    vec![
+                        comment("a1", "t1_a", vec![comment("a1a", "t1_a1", vec![])]),
+                        fixtures::more("m1", "t1_a", &["a2", "a3"]),
+                    ],
+                ),
+                comment("b", "t3_mock01", vec![]),
+                fixtures::more("m2", "t3_mock01", &["c"]),
+            ],
+            None,
+        ),
+    ])
+}
+
+fn ids(tree: &CommentTree) -> Vec<String> {
+    tree.comments().map(|comment| comment.id.clone()).collect()
+}
+
+#[test]
+fn builds_tree() -> anyhow::Result<()> {
+    let tree = CommentTree::from_response(comments_response())?;
+    assert_eq!(tree.link_id, "t3_mock01");
+    assert_eq!(ids(&tree), ["a", "a1", "a1a", "b"]);
+    assert_eq!(tree.len(), 6);
+    assert_eq!(tree.roots().len(), 3);
+    assert_eq!(tree.more().len(), 2);
+
+    let a1a = tree.find("a1a").unwrap();
+    assert_eq!(tree.get(a1a).depth, 2);
+    let a1 = tree.parent(a1a).unwrap();
+    assert_eq!(tree.get(a1).comment().unwrap().id, "a1");
+    assert_eq!(tree.children(a1), [a1a]);
+    assert_eq!(tree.find("t1_a"), tree.parent(a1));
+    assert!(tree.parent(tree.find("a").unwrap()).is_none());
+    Ok(())
+}
+
+/// A retriever outside of rraw that only implements `get_comments`
+struct CannedComments;
+
+#[async_trait::async_trait(?Send)]
+impl CommentRetriever for CannedComments {
+    async fn get_comments(&self, _sort: Option<CommentOption>) -> Result<ListingArray, Error> {
+        Ok(serde_json::from_value(comments_response())?)
+    }
+}
+
+#[tokio::test]
+async fn default_comment_tree() -> anyhow::Result<()> {
+    init();
+    let tree = CannedComments.get_comment_tree(None).await?;
+    assert_eq!(tree.link_id, "t3_mock01");
+    assert_eq!(ids(&tree), ["a", "a1", "a1a", "b"]);
+    assert_eq!(tree.get(tree.find("a1a").unwrap()).depth, 2);
+    // The stub in the replies of a is kept below a
+    let more = tree.more();
+    assert_eq!(more.len(), 2);
+    assert_eq!(tree.parent(more[0]), tree.find("a"));
+    assert_eq!(tree.get(more[0]).more().unwrap().children, ["a2", "a3"]);
+    assert_eq!(tree.get(more[1]).more().unwrap().children, ["c"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn expands_nested_more() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    reddit.respond_once(
+        "GET",
+        "/api/morechildren",
+        MockResponse::json(&fixtures::api_json(json!({
+            "things": [comment("a2", "t1_a", vec![]), comment("a3", "t1_a", vec![])]
+        }))),
+    );
+    let mut tree = CannedComments.get_comment_tree(None).await?;
+    let stub = tree.more()[0];
+    assert_eq!(tree.expand_more(&client, 1).await?, 1);
+    assert_eq!(ids(&tree), ["a", "a1", "a1a", "a2", "a3", "b"]);
+    assert_eq!(tree.parent(tree.find("a2").unwrap()), tree.find("a"));
+
+    // The expanded stub is a tombstone outside of the tree
+    assert!(matches!(tree.get(stub).kind, CommentNodeKind::Expanded));
+    assert!(tree.get(stub).more().is_none());
+    assert!(!tree.iter().any(|id| id == stub));
+    assert_eq!(tree.more().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stub_names_do_not_hide_comments() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    reddit.respond_once(
+        "GET",
+        "/api/morechildren",
+        MockResponse::json(&fixtures::api_json(json!({
+            "things": [comment("a2", "t1_a", vec![]), comment("a3", "t1_a", vec![])]
+        }))),
+    );
+    // Reddit names a stub after the first comment it hides
+    let response = json!([
+        fixtures::listing(vec![fixtures::link("mock01", "rust")], None),
+        fixtures::listing(
+            vec![comment(
+                "a",
+                "t3_mock01",
+                vec![fixtures::more("a2", "t1_a", &["a2", "a3"])]
+            )],
+            None,
+        ),
+    ]);
+    let mut tree = CommentTree::from_response(response)?;
+    assert!(tree.find("a2").is_none());
+    tree.expand_more(&client, 1).await?;
+    let a2 = tree.find("t1_a2").unwrap();
+    assert_eq!(tree.get(a2).comment().unwrap().id, "a2");
+    Ok(())
+}
+
+#[tokio::test]
+async fn expands_more() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    reddit.respond(
+        "GET",
+        "/r/rust/comments/mock01/mock_submission",
+        MockResponse::json(&comments_response()),
+    );
+    reddit.respond_once(
+        "GET",
+        "/api/morechildren",
+        MockResponse::json(&fixtures::api_json(json!({
+            "things": [
+                comment("a2", "t1_a", vec![]),
+                comment("a2a", "t1_a2", vec![]),
+                comment("a3", "t1_a", vec![]),
+            ]
+        }))),
+    );
+    reddit.respond_once(
+        "GET",
+        "/api/morechildren",
+        MockResponse::json(&fixtures::api_json(json!({
+            "things": [comment("c", "t3_mock01", vec![])]
+        }))),
+    );
+    let subreddit = client.subreddit("rust").await?;
+    let hot = subreddit.hot(None).await?;
+    let submission = hot.data.children[0].data.to_submission(&client);
+    let mut tree = submission.get_comment_tree(None).await?;
+
+    assert_eq!(tree.expand_more(&client, 1).await?, 1);
+    assert_eq!(ids(&tree), ["a", "a1", "a1a", "a2", "a2a", "a3", "b"]);
+    assert_eq!(tree.get(tree.find("a2a").unwrap()).depth, 2);
+    assert_eq!(tree.more().len(), 1);
+
+    assert_eq!(tree.expand_more(&client, 5).await?, 1);
+    assert_eq!(ids(&tree), ["a", "a1", "a1a", "a2", "a2a", "a3", "b", "c"]);
+    assert!(tree.more().is_empty());
+
+    let requests = reddit.requests_to("/api/morechildren");
+    let query = requests[0].query_pairs();
+    assert_eq!(query["link_id"], "t3_mock01");
+    assert_eq!(query["children"], "a2,a3");
+    assert_eq!(requests[1].query_pairs()["children"], "c");
+    Ok(())
+}
+
+#[test]
+fn typed_replies() -> anyhow::Result<()> {
+    let mut thread = comment(
+        "a",
+        "t3_mock01",
+        vec![
+            comment("a1", "t1_a", vec![comment("a1a", "t1_a1", vec![])]),
+            comment("a2", "t1_a", vec![]),
+            fixtures::more("m1", "t1_a", &["a3"]),
+        ],
+    );
+    thread["data"]["replies"]["data"]["children"][1]["data"]["replies"] = json!("");
+    let thread: GenericResponse<CommentResponse> = serde_json::from_value(thread)?;
+    let thread = thread.data;
+
+    let replies: Vec<&str> = thread.children().map(|reply| reply.id.as_str()).collect();
+    assert_eq!(replies, ["a1", "a2"]);
+    let walked: Vec<(u32, &str)> = thread
+        .walk()
+        .map(|(depth, comment)| (depth, comment.id.as_str()))
+        .collect();
+    assert_eq!(walked, [(0, "a"), (1, "a1"), (2, "a1a"), (1, "a2")]);
+    assert_eq!(thread.flatten().len(), 4);
+    assert_eq!(
+        thread.find("t1_a1a").unwrap().parent_id.as_deref(),
+        Some("t1_a1")
+    );
+    assert!(thread.find("a2").unwrap().replies.is_none());
+    assert!(thread.find("a3").is_none());
+    // The stub is kept next to the comments
+    let more: Vec<&MoreChildren> = thread.more().collect();
+    assert_eq!(more.len(), 1);
+    assert_eq!(more[0].parent_id, "t1_a");
+    assert_eq!(more[0].children, ["a3"]);
+    assert!(thread.find("a1").unwrap().more().next().is_none());
+    Ok(())
+}
+
+#[test]
+fn invalid_replies() {
+    for replies in [json!(null), json!(1), json!("a"), json!([])] {
+        let mut thread = comment("a", "t3_mock01", vec![]);
+        thread["data"]["replies"] = replies.clone();
+        let error = serde_json::from_value::<GenericResponse<CommentResponse>>(thread).unwrap_err();
+        assert!(
+            error.to_string().contains("replies"),
+            "{}: {}",
+            replies,
+            error
+        );
+    }
+    let mut thread = comment("a", "t3_mock01", vec![]);
+    thread["data"]["replies"] = fixtures::listing(vec![fixtures::link("mock01", "rust")], None);
+    assert!(serde_json::from_value::<GenericResponse<CommentResponse>>(thread).is_err());
+}
@@ -1,6 +1,6 @@
 use log::LevelFilter;
 use rraw::auth::AnonymousAuthenticator;
-use rraw::Client;
+use rraw::testing::MockReddit;
 
 fn init() {
     if let Err(error) = env_logger::builder()
@@ -12,15 +12,19 @@ fn init() {
     }
 }
 
-#[ignore]
 #[tokio::test]
 async fn generic() -> anyhow::Result<()> {
     init();
-    let client = Client::login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
 
     let domains = client.domain("rust-lang.org", None).await;
     assert!(domains.is_ok());
     let data = domains.unwrap().data;
-    assert!(data.children.len() > 0);
-    return Ok(());
+    assert!(!data.children.is_empty());
+    assert_eq!(reddit.requests()[0].path, "/domain/rust-lang.org.json");
+    Ok(())
 }
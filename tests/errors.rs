@@ -0,0 +1,121 @@
+use log::LevelFilter;
+use rraw::error::http_error::{HTTPError, MAX_BODY_LENGTH};
+use rraw::error::Error;
+use rraw::retry::RetryPolicy;
+use rraw::testing::{MockReddit, MockResponse};
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+#[tokio::test]
+async fn http_error_context() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.retry_policy(RetryPolicy::none()))
+        .await?;
+    let unauthorized = MockResponse::new(401, r#"{"message": "Unauthorized", "error": 401}"#)
+        .with_header(
+            "WWW-Authenticate",
+            "Bearer realm=\"reddit\", error=\"invalid_token\"",
+        )
+        .with_header("Content-Type", "application/json");
+    // Rejected again after the token was refreshed
+    reddit.respond_once("GET", "/api/v1/me", unauthorized.clone());
+    reddit.respond_once("GET", "/api/v1/me", unauthorized);
+
+    let error = client.me().await.err().expect("Expected an error");
+    assert!(error.is_auth_failure());
+    assert!(!error.is_retryable());
+    match error {
+        Error::HTTPError(HTTPError::Unauthorized(response)) => {
+            assert_eq!(response.status, 401);
+            assert_eq!(response.method, "GET");
+            assert_eq!(response.path, "/api/v1/me");
+            assert!(response.body.contains("Unauthorized"));
+            assert!(response.headers.contains_key("www-authenticate"));
+            assert!(!response.headers.contains_key("content-type"));
+        }
+        error => panic!("Unexpected Error {}", error),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn error_helpers() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.retry_policy(RetryPolicy::none()))
+        .await?;
+    let subreddit = client.subreddit("rust").await?;
+
+    reddit.respond_once("POST", "/api/compose", MockResponse::new(403, "Forbidden"));
+    let error = subreddit
+        .compose("a".to_string(), "b".to_string(), "c".to_string())
+        .await
+        .unwrap_err();
+    assert!(error.is_forbidden());
+    assert!(!error.is_auth_failure());
+    match &error {
+        Error::HTTPError(error) => {
+            let response = error.response().unwrap();
+            assert_eq!(response.method, "POST");
+            assert_eq!(response.path, "/api/compose");
+        }
+        error => panic!("Unexpected Error {}", error),
+    }
+
+    reddit.respond_once(
+        "GET",
+        "/r/rust/about",
+        MockResponse::new(429, "").with_header("x-ratelimit-remaining", "0"),
+    );
+    let error = client
+        .subreddit("rust")
+        .await
+        .err()
+        .expect("Expected an error");
+    assert!(error.is_retryable());
+    match error {
+        Error::HTTPError(HTTPError::TooManyRequests(response)) => {
+            assert_eq!(response.headers["x-ratelimit-remaining"], "0");
+        }
+        error => panic!("Unexpected Error {}", error),
+    }
+
+    reddit.respond_once(
+        "GET",
+        "/r/rust/about",
+        MockResponse::new(503, "a".repeat(MAX_BODY_LENGTH * 2)),
+    );
+    let error = client
+        .subreddit("rust")
+        .await
+        .err()
+        .expect("Expected an error");
+    assert!(error.is_retryable());
+    match error {
+        Error::HTTPError(HTTPError::ServerError(response)) => {
+            assert_eq!(response.body.len(), MAX_BODY_LENGTH + 3);
+        }
+        error => panic!("Unexpected Error {}", error),
+    }
+
+    reddit.respond_once("GET", "/r/rust/about", MockResponse::new(404, ""));
+    let error = client
+        .subreddit("rust")
+        .await
+        .err()
+        .expect("Expected an error");
+    assert!(matches!(error, Error::HTTPError(HTTPError::NotFound)));
+    assert!(!error.is_retryable());
+    Ok(())
+}
@@ -1,9 +1,16 @@
 #[cfg(test)]
 mod me_tests {
+    use futures_util::{StreamExt, TryStreamExt};
     use log::LevelFilter;
-    use rraw::auth::{CodeAuthenticator, PasswordAuthenticator, TokenAuthenticator};
+    use rraw::auth::{CodeAuthenticator, TokenAuthenticator};
+    use rraw::builder::Endpoints;
     use rraw::message::WhereMessage;
+    use rraw::responses::FullName;
+    use rraw::stream::StreamOptions;
+    use rraw::testing::{fixtures, MockReddit, MockResponse};
     use rraw::Client;
+    use std::str::FromStr;
+    use std::time::Duration;
 
     fn init() {
         if let Err(error) = env_logger::builder()
@@ -15,22 +22,14 @@ mod me_tests {
         }
     }
 
-    async fn create_client_by_pass() -> anyhow::Result<Client<PasswordAuthenticator>> {
-        dotenv::dotenv()?;
-        let arc = PasswordAuthenticator::new(
-            std::env::var("CLIENT_KEY_BY_PASS")?.as_str(),
-            std::env::var("CLIENT_SECRET_BY_PASS")?.as_str(),
-            std::env::var("REDDIT_USER")?.as_str(),
-            std::env::var("PASSWORD")?.as_str(),
-        );
-        Ok(Client::login(arc, "RRAW Test (by u/KingTuxWH)").await?)
-    }
-
-    #[ignore]
     #[tokio::test]
     async fn me_test_by_pass() -> anyhow::Result<()> {
         init();
-        let client = create_client_by_pass().await?;
+        let reddit = MockReddit::start()?;
+        let client = reddit.password_client().await?;
+        let login = &reddit.requests_to("/api/v1/access_token")[0];
+        assert_eq!(login.form()["grant_type"], "password");
+        assert_eq!(login.form()["username"], "rraw_test");
 
         let me = client.me().await;
 
@@ -39,15 +38,20 @@ mod me_tests {
         assert!(me.saved(None).await.is_ok());
         assert!(me.up_voted(None).await.is_ok());
         assert!(me.down_voted(None).await.is_ok());
+        let request = &reddit.requests_to("/api/v1/me")[0];
+        assert_eq!(
+            request.header("Authorization"),
+            Some("Bearer mock_access_token")
+        );
 
-        return Ok(());
+        Ok(())
     }
 
-    #[ignore]
     #[tokio::test]
     async fn test_inbox_by_pass() -> anyhow::Result<()> {
         init();
-        let client = create_client_by_pass().await?;
+        let reddit = MockReddit::start()?;
+        let client = reddit.password_client().await?;
 
         let me = client.me().await?;
 
@@ -58,25 +62,131 @@ mod me_tests {
         me.get_messages(Some(WhereMessage::Unread), None)
             .await
             .unwrap();
-        return Ok(());
+        assert_eq!(reddit.requests_to("/message/unread").len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_by_pass() -> anyhow::Result<()> {
+        init();
+        let reddit = MockReddit::start()?;
+        let client = reddit.password_client().await?;
+
+        let me = client.me().await?;
+        me.compose(
+            "rraw_friend".to_string(),
+            "Hello".to_string(),
+            "World".to_string(),
+        )
+        .await?;
+        assert!(
+            me.block_author(FullName::from_str("t4_mockm1")?)
+                .await?
+                .success
+        );
+
+        let compose = &reddit.requests_to("/api/compose")[0];
+        assert_eq!(compose.method, "POST");
+        assert_eq!(compose.form()["to"], "rraw_friend");
+        assert_eq!(
+            reddit.requests_to("/api/block")[0].form()["id"],
+            "t4_mockm1"
+        );
+        Ok(())
     }
 
-    async fn create_client_by_code() -> anyhow::Result<Client<CodeAuthenticator>> {
-        dotenv::dotenv()?;
+    #[tokio::test]
+    async fn test_stream_inbox_by_pass() -> anyhow::Result<()> {
+        init();
+        let reddit = MockReddit::start()?;
+        let client = reddit.password_client().await?;
+        let unread = |ids: &[&str]| {
+            let children = ids.iter().map(|id| fixtures::message(id)).collect();
+            MockResponse::json(&fixtures::listing(children, None))
+        };
+        reddit.respond_once("GET", "/message/unread", unread(&["m2", "m1"]));
+        reddit.respond("GET", "/message/unread", unread(&["m3", "m2", "m1"]));
+        let options = StreamOptions {
+            min_interval: Duration::from_millis(10),
+            max_interval: Duration::from_millis(40),
+            ..StreamOptions::default()
+        };
+
+        let me = client.me().await?;
+        let messages: Vec<_> = me
+            .stream_inbox(Some(options), true)
+            .take(3)
+            .try_collect()
+            .await?;
+
+        let ids: Vec<&str> = messages.iter().map(|m| m.data.id.as_str()).collect();
+        assert_eq!(ids, ["m1", "m2", "m3"]);
+        let read: Vec<String> = reddit
+            .requests_to("/api/read_message")
+            .iter()
+            .map(|request| request.form()["id"].clone())
+            .collect();
+        // One request per poll
+        assert_eq!(read, ["t4_m1,t4_m2", "t4_m3"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_inbox_mark_read_fails() -> anyhow::Result<()> {
+        init();
+        let reddit = MockReddit::start()?;
+        let client = reddit.password_client().await?;
+        let unread =
+            fixtures::listing(vec![fixtures::message("m2"), fixtures::message("m1")], None);
+        reddit.respond("GET", "/message/unread", MockResponse::json(&unread));
+        reddit.respond_once("POST", "/api/read_message", MockResponse::new(500, ""));
+        let options = StreamOptions {
+            min_interval: Duration::from_millis(10),
+            max_interval: Duration::from_millis(40),
+            ..StreamOptions::default()
+        };
+
+        let me = client.me().await?;
+        let messages: Vec<_> = me.stream_inbox(Some(options), true).take(3).collect().await;
+        // The messages are only yielded once they were marked read
+        assert!(messages[0].is_err());
+        let ids: Vec<&str> = messages[1..]
+            .iter()
+            .map(|message| message.as_ref().unwrap().data.id.as_str())
+            .collect();
+        assert_eq!(ids, ["m1", "m2"]);
+        let read: Vec<String> = reddit
+            .requests_to("/api/read_message")
+            .iter()
+            .map(|request| request.form()["id"].clone())
+            .collect();
+        assert_eq!(read, ["t4_m1,t4_m2", "t4_m1,t4_m2"]);
+        Ok(())
+    }
+
+    async fn create_client_by_code(
+        reddit: &MockReddit,
+    ) -> anyhow::Result<Client<CodeAuthenticator>> {
         let arc = CodeAuthenticator::new(
-            std::env::var("CLIENT_KEY_BY_CODE")?.as_str(),
-            std::env::var("CLIENT_SECRET_BY_CODE")?.as_str(),
-            std::env::var("CODE")?.as_str(),
-            std::env::var("REDIRECT_URI")?.as_str(),
+            "client_id",
+            "client_secret",
+            "code#_",
+            "http://localhost:8080",
         );
-        Ok(Client::login(arc, "RRAW Test (by u/KingTuxWH)").await?)
+        Ok(reddit
+            .client_builder("RRAW Test (by u/KingTuxWH)")
+            .login(arc)
+            .await?)
     }
 
-    #[ignore]
     #[tokio::test]
     async fn me_test_by_code() -> anyhow::Result<()> {
         init();
-        let client = create_client_by_code().await?;
+        let reddit = MockReddit::start()?;
+        let client = create_client_by_code(&reddit).await?;
+        let login = &reddit.requests_to("/api/v1/access_token")[0];
+        assert_eq!(login.form()["grant_type"], "authorization_code");
+        assert_eq!(login.form()["code"], "code");
 
         let me = client.me().await;
 
@@ -86,36 +196,46 @@ mod me_tests {
         assert!(me.up_voted(None).await.is_ok());
         assert!(me.down_voted(None).await.is_ok());
 
-        let r_t = client.refresh_token();
-        if r_t.is_some() {
-            println!("Refresh Token Is: {}", r_t.unwrap())
-        } else {
-            println!("Refresh Token Not Exist!")
-        }
+        assert_eq!(
+            client.refresh_token().as_deref(),
+            Some("mock_refresh_token")
+        );
 
-        return Ok(());
+        Ok(())
     }
 
-    #[ignore]
     #[test]
     fn code_link() -> anyhow::Result<()> {
-        dotenv::dotenv()?;
         let string = CodeAuthenticator::generate_authorization_url(
-            std::env::var("CLIENT_KEY_BY_CODE").unwrap(),
-            std::env::var("REDIRECT_URI").unwrap(),
+            &Endpoints::default(),
+            "client_id",
+            "http://localhost:8080",
             "my_state",
             "temporary",
             vec!["identity", "read", "save", "history"],
         );
         println!("{}", string);
-        return Ok(());
+        assert!(string.contains("state=my_state"));
+        assert!(string.contains("scope=identity,read,save,history"));
+        assert!(string.starts_with("https://reddit.com/api/v1/authorize?"));
+
+        let string = CodeAuthenticator::generate_authorization_url(
+            &Endpoints::with_base("http://127.0.0.1:8080"),
+            "client_id",
+            "http://localhost:8080",
+            "my_state",
+            "temporary",
+            vec!["identity"],
+        );
+        assert!(string.starts_with("http://127.0.0.1:8080/api/v1/authorize?"));
+        Ok(())
     }
 
-    #[ignore]
     #[tokio::test]
     async fn test_inbox_by_code() -> anyhow::Result<()> {
         init();
-        let client = create_client_by_code().await?;
+        let reddit = MockReddit::start()?;
+        let client = create_client_by_code(&reddit).await?;
 
         let me = client.me().await?;
 
@@ -126,24 +246,27 @@ mod me_tests {
         me.get_messages(Some(WhereMessage::Unread), None)
             .await
             .unwrap();
-        return Ok(());
+        Ok(())
     }
 
-    async fn create_client_by_token() -> anyhow::Result<Client<TokenAuthenticator>> {
-        dotenv::dotenv()?;
-        let arc = TokenAuthenticator::new(
-            std::env::var("CLIENT_KEY_BY_TOKEN")?.as_str(),
-            std::env::var("CLIENT_SECRET_BY_TOKEN")?.as_str(),
-            std::env::var("REFRESH_TOKEN")?.as_str(),
-        );
-        Ok(Client::login(arc, "RRAW Test (by u/KingTuxWH)").await?)
+    async fn create_client_by_token(
+        reddit: &MockReddit,
+    ) -> anyhow::Result<Client<TokenAuthenticator>> {
+        let arc = TokenAuthenticator::new("client_id", "client_secret", "refresh_token");
+        Ok(reddit
+            .client_builder("RRAW Test (by u/KingTuxWH)")
+            .login(arc)
+            .await?)
     }
 
-    #[ignore]
     #[tokio::test]
     async fn me_test_by_token() -> anyhow::Result<()> {
         init();
-        let client = create_client_by_token().await?;
+        let reddit = MockReddit::start()?;
+        let client = create_client_by_token(&reddit).await?;
+        let login = &reddit.requests_to("/api/v1/access_token")[0];
+        assert_eq!(login.form()["grant_type"], "refresh_token");
+        assert_eq!(login.form()["refresh_token"], "refresh_token");
 
         let me = client.me().await;
 
@@ -153,21 +276,16 @@ mod me_tests {
         assert!(me.up_voted(None).await.is_ok());
         assert!(me.down_voted(None).await.is_ok());
 
-        let r_t = client.refresh_token();
-        if r_t.is_some() {
-            println!("Refresh Token Is: {}", r_t.unwrap())
-        } else {
-            println!("Refresh Token Not Exist!")
-        }
+        assert_eq!(client.refresh_token().as_deref(), Some("refresh_token"));
 
-        return Ok(());
+        Ok(())
     }
 
-    #[ignore]
     #[tokio::test]
     async fn test_inbox_by_token() -> anyhow::Result<()> {
         init();
-        let client = create_client_by_token().await?;
+        let reddit = MockReddit::start()?;
+        let client = create_client_by_token(&reddit).await?;
 
         let me = client.me().await?;
 
@@ -178,6 +296,6 @@ mod me_tests {
         me.get_messages(Some(WhereMessage::Unread), None)
             .await
             .unwrap();
-        return Ok(());
+        Ok(())
     }
 }
@@ -0,0 +1,105 @@
+use futures_util::TryStreamExt;
+use log::LevelFilter;
+use rraw::auth::AnonymousAuthenticator;
+use rraw::responses::listing::paginator::paginate;
+use rraw::submission::SubmissionRetriever;
+use rraw::testing::{fixtures, MockReddit, MockResponse};
+use rraw::utils::options::FeedOption;
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+fn page(ids: &[&str], after: Option<&str>) -> MockResponse {
+    let children = ids.iter().map(|id| fixtures::link(id, "rust")).collect();
+    MockResponse::json(&fixtures::listing(children, after))
+}
+
+#[tokio::test]
+async fn follows_after() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    let subreddit = client.subreddit("rust").await?;
+    reddit.respond_once("GET", "/r/rust/new", page(&["a", "b"], Some("t3_b")));
+    reddit.respond_once("GET", "/r/rust/new", page(&["c"], Some("t3_c")));
+    reddit.respond_once("GET", "/r/rust/new", page(&[], None));
+
+    let feed = FeedOption {
+        limit: Some(2),
+        ..FeedOption::default()
+    };
+    let submissions: Vec<_> = paginate(Some(feed), None, |feed| {
+        subreddit.get_submissions("new", Some(feed))
+    })
+    .try_collect()
+    .await?;
+
+    let ids: Vec<&str> = submissions.iter().map(|s| s.data.id.as_str()).collect();
+    assert_eq!(ids, ["a", "b", "c"]);
+    let requests = reddit.requests_to("/r/rust/new");
+    assert_eq!(requests.len(), 3);
+    assert!(!requests[0].query_pairs().contains_key("after"));
+    assert_eq!(requests[1].query_pairs()["after"], "t3_b");
+    assert_eq!(requests[1].query_pairs()["count"], "2");
+    assert_eq!(requests[1].query_pairs()["limit"], "2");
+    assert_eq!(requests[2].query_pairs()["after"], "t3_c");
+    Ok(())
+}
+
+#[tokio::test]
+async fn stops_at_max_items() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    let subreddit = client.subreddit("rust").await?;
+    reddit.respond("GET", "/r/rust/new", page(&["a", "b"], Some("t3_b")));
+
+    let submissions: Vec<_> = paginate(None, Some(3), |feed| {
+        subreddit.get_submissions("new", Some(feed))
+    })
+    .try_collect()
+    .await?;
+
+    assert_eq!(submissions.len(), 3);
+    assert_eq!(reddit.requests_to("/r/rust/new").len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn ends_on_error() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    reddit.respond_once(
+        "GET",
+        "/subreddits/search",
+        MockResponse::json(&fixtures::listing(
+            vec![fixtures::subreddit("rust")],
+            Some("t5_rust"),
+        )),
+    );
+    reddit.respond_once("GET", "/subreddits/search", MockResponse::new(404, ""));
+
+    let mut stream = Box::pin(paginate(None, None, |feed| {
+        client.search_subreddits("rust", None, Some(feed))
+    }));
+    assert!(stream.try_next().await?.is_some());
+    assert!(stream.try_next().await.is_err());
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+use log::LevelFilter;
+use rraw::auth::AnonymousAuthenticator;
+use rraw::testing::{fixtures, MockReddit, MockResponse};
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+fn limited_user(remaining: &str, used: &str, reset: &str) -> MockResponse {
+    MockResponse::json(&fixtures::account("KingTuxWH"))
+        .with_header("X-Ratelimit-Remaining", remaining)
+        .with_header("X-Ratelimit-Used", used)
+        .with_header("X-Ratelimit-Reset", reset)
+}
+
+#[tokio::test]
+async fn tracks_headers() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    assert!(client.rate_limit().is_none());
+
+    reddit.respond(
+        "GET",
+        "/u/KingTuxWH/about",
+        limited_user("598.0", "2", "300"),
+    );
+    client.user("KingTuxWH").await?;
+
+    let rate_limit = client.rate_limit().expect("Rate Limit was not tracked");
+    assert_eq!(rate_limit.remaining, 598.0);
+    assert_eq!(rate_limit.used, 2);
+    assert_eq!(rate_limit.reset, 300);
+    assert!(!rate_limit.is_exhausted());
+    Ok(())
+}
+
+#[tokio::test]
+async fn waits_when_exhausted() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .wait_on_rate_limit(true)
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    reddit.respond("GET", "/u/KingTuxWH/about", limited_user("0.0", "600", "1"));
+    client.user("KingTuxWH").await?;
+    assert!(client.rate_limit().unwrap().is_exhausted());
+
+    let start = Instant::now();
+    client.user("KingTuxWH").await?;
+    assert!(start.elapsed() >= Duration::from_millis(900));
+    Ok(())
+}
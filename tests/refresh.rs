@@ -0,0 +1,317 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::LevelFilter;
+use serde_json::json;
+
+use rraw::auth::{
+    CodeAuthenticator, MemoryTokenStore, StoredToken, TokenAuthenticator, TokenStore,
+};
+use rraw::error::Error;
+use rraw::testing::{fixtures, MockReddit, MockResponse};
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+/// A token that expires in `expires_in` seconds
+fn token(expires_in: u64) -> MockResponse {
+    let mut token = fixtures::access_token();
+    token["expires_in"] = json!(expires_in);
+    MockResponse::json(&token)
+}
+
+/// A token with its own access token. So the requests show which token they were sent with
+fn named_token(access_token: &str, expires_in: u64) -> MockResponse {
+    let mut token = fixtures::access_token();
+    token["access_token"] = json!(access_token);
+    token["expires_in"] = json!(expires_in);
+    MockResponse::json(&token)
+}
+
+#[tokio::test]
+async fn refreshes_before_expiry() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    reddit.respond_once("POST", "/api/v1/access_token", token(30));
+    let client = reddit.password_client().await?;
+
+    // 30 seconds left is within the default margin of 60 seconds
+    client.me().await?;
+    let logins = reddit.requests_to("/api/v1/access_token");
+    assert_eq!(logins.len(), 2);
+    assert_eq!(logins[1].form()["grant_type"], "password");
+    assert_eq!(logins[1].form()["username"], "rraw_test");
+
+    client.me().await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn configurable_margin() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    reddit.respond_once("POST", "/api/v1/access_token", token(30));
+    let client = reddit
+        .password_client_with(|builder| builder.refresh_margin(Duration::from_secs(10)))
+        .await?;
+    client.me().await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn missing_refresh_token() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    reddit.respond_once(
+        "POST",
+        "/api/v1/access_token",
+        MockResponse::json(&json!({
+            "access_token": "mock_access_token",
+            "expires_in": 0,
+            "scope": "*",
+            "token_type": "bearer",
+        })),
+    );
+    let auth = CodeAuthenticator::new("client_id", "client_secret", "code", "http://localhost");
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth)
+        .await?;
+    assert_eq!(client.refresh_token(), None);
+    match client.me().await.err().expect("The token expired") {
+        Error::NoRefreshToken => {}
+        error => panic!("Unexpected Error {}", error),
+    }
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+    Ok(())
+}
+
+/// Needs the lock shared by all requests. Without `shared_authentication` every request refreshes on its own
+#[cfg(feature = "shared_authentication")]
+#[tokio::test]
+async fn single_flight_refresh() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.replay_non_idempotent(false))
+        .await?;
+    // The forced refresh holds the lock while the requests wait for it. It receives a token that
+    // has already expired, so every waiting request sees an expired token once the lock is released
+    reddit.respond_once("POST", "/api/v1/access_token", token(0));
+    let requests = 4;
+    let forced = async { client.re_login().await.map(|_| ()) };
+    let waiting = (0..requests).map(|_| async { client.subreddit("rust").await.map(|_| ()) });
+    let (forced, waiting) = tokio::join!(forced, futures_util::future::join_all(waiting));
+    forced?;
+    for result in waiting {
+        result?;
+    }
+    // The login, the forced refresh and a single refresh for the waiting requests
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 3);
+    assert_eq!(reddit.requests_to("/r/rust/about").len(), requests);
+    Ok(())
+}
+
+fn invalid_token() -> MockResponse {
+    MockResponse::new(401, "").with_header(
+        "WWW-Authenticate",
+        "Bearer realm=\"reddit\", error=\"invalid_token\"",
+    )
+}
+
+#[tokio::test]
+async fn replays_after_invalid_token() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.replay_non_idempotent(false))
+        .await?;
+    reddit.respond_once("GET", "/api/v1/me", invalid_token());
+
+    client.me().await?;
+    assert_eq!(reddit.requests_to("/api/v1/me").len(), 2);
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 2);
+
+    // Replayed once. A second rejection is returned to the caller
+    reddit.respond_once("GET", "/api/v1/me", invalid_token());
+    reddit.respond_once("GET", "/api/v1/me", invalid_token());
+    let error = client
+        .me()
+        .await
+        .err()
+        .expect("The rejection was not returned");
+    assert!(error.is_auth_failure());
+    assert_eq!(reddit.requests_to("/api/v1/me").len(), 4);
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 3);
+
+    // Other 401s are not caused by the token
+    reddit.respond_once("GET", "/api/v1/me", MockResponse::new(401, ""));
+    assert!(client.me().await.is_err());
+    assert_eq!(reddit.requests_to("/api/v1/me").len(), 5);
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn replays_post_only_when_enabled() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.replay_non_idempotent(false))
+        .await?;
+    let me = client.me().await?;
+    reddit.respond_once("POST", "/api/compose", invalid_token());
+    let result = me
+        .compose(
+            "rraw_friend".to_string(),
+            "Hello".to_string(),
+            "World".to_string(),
+        )
+        .await;
+    assert!(result.unwrap_err().is_auth_failure());
+    assert_eq!(reddit.requests_to("/api/compose").len(), 1);
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+
+    let client = reddit
+        .password_client_with(|builder| builder.replay_non_idempotent(true))
+        .await?;
+    let me = client.me().await?;
+    reddit.clear_requests();
+    reddit.respond_once("POST", "/api/compose", invalid_token());
+    me.compose(
+        "rraw_friend".to_string(),
+        "Hello".to_string(),
+        "World".to_string(),
+    )
+    .await?;
+    let composed = reddit.requests_to("/api/compose");
+    assert_eq!(composed.len(), 2);
+    assert_eq!(composed[0].form(), composed[1].form());
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+    Ok(())
+}
+
+async fn store_client(
+    reddit: &MockReddit,
+    store: &MemoryTokenStore,
+    refresh_margin: Duration,
+) -> anyhow::Result<rraw::Client<TokenAuthenticator>> {
+    let auth = TokenAuthenticator::new("client_id", "client_secret", "refresh_token")
+        .with_store(store.clone());
+    Ok(reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .refresh_margin(refresh_margin)
+        .login(auth)
+        .await?)
+}
+
+#[tokio::test]
+async fn store_refreshes_within_margin() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let store = MemoryTokenStore::new();
+    reddit.respond_once(
+        "POST",
+        "/api/v1/access_token",
+        named_token("first_access_token", 90),
+    );
+    let client = store_client(&reddit, &store, Duration::from_secs(120)).await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+
+    // The store only has the token that is about to expire. Reddit is asked for a new one
+    client.me().await?;
+    let logins = reddit.requests_to("/api/v1/access_token");
+    assert_eq!(logins.len(), 2);
+    assert_eq!(logins[1].form()["grant_type"], "refresh_token");
+    let me = reddit.requests_to("/api/v1/me");
+    assert_eq!(
+        me[0].header("authorization"),
+        Some("Bearer mock_access_token")
+    );
+    let stored = store.load().await?.expect("Token was not saved");
+    assert_eq!(stored.token.as_deref(), Some("mock_access_token"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn store_refreshes_after_invalid_token() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let store = MemoryTokenStore::new();
+    reddit.respond_once(
+        "POST",
+        "/api/v1/access_token",
+        named_token("first_access_token", 3600),
+    );
+    let client = store_client(&reddit, &store, Duration::from_secs(60)).await?;
+    reddit.respond_once("GET", "/api/v1/me", invalid_token());
+
+    // The store has the rejected token. Reddit is asked for a new one
+    client.me().await?;
+    let logins = reddit.requests_to("/api/v1/access_token");
+    assert_eq!(logins.len(), 2);
+    assert_eq!(logins[1].form()["grant_type"], "refresh_token");
+    assert_eq!(reddit.requests_to("/api/v1/me").len(), 2);
+    let stored = store.load().await?.expect("Token was not saved");
+    assert_eq!(stored.token.as_deref(), Some("mock_access_token"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn store_replays_with_new_token() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let store = MemoryTokenStore::new();
+    reddit.respond_once(
+        "POST",
+        "/api/v1/access_token",
+        named_token("first_access_token", 3600),
+    );
+    let client = store_client(&reddit, &store, Duration::from_secs(60)).await?;
+    reddit.respond_once("GET", "/api/v1/me", invalid_token());
+    client.me().await?;
+    let me = reddit.requests_to("/api/v1/me");
+    assert_eq!(me.len(), 2);
+    assert_eq!(
+        me[0].header("authorization"),
+        Some("Bearer first_access_token")
+    );
+    assert_eq!(
+        me[1].header("authorization"),
+        Some("Bearer mock_access_token")
+    );
+
+    // Another worker sharing the store already refreshed it. Its token is used for the replay
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    store
+        .save(&StoredToken {
+            token: Some("other_access_token".to_string()),
+            expiration_time: Some(now + 3_600_000),
+            refresh_token: Some("refresh_token".to_string()),
+            scope: Some("*".to_string()),
+        })
+        .await?;
+    reddit.clear_requests();
+    reddit.respond_once("GET", "/api/v1/me", invalid_token());
+    client.me().await?;
+    let me = reddit.requests_to("/api/v1/me");
+    assert_eq!(me.len(), 2);
+    assert_eq!(
+        me[0].header("authorization"),
+        Some("Bearer mock_access_token")
+    );
+    assert_eq!(
+        me[1].header("authorization"),
+        Some("Bearer other_access_token")
+    );
+    assert!(reddit.requests_to("/api/v1/access_token").is_empty());
+    Ok(())
+}
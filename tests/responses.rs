@@ -0,0 +1,210 @@
+use log::LevelFilter;
+use rraw::responses::{FullName, Lenient, RedditDataType, RedditResponse, RedditTypeResponse};
+use rraw::testing::{fixtures, MockReddit, MockResponse};
+use serde_json::json;
+use std::str::FromStr;
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+#[test]
+fn schema_drift_is_an_error() {
+    let mut link = fixtures::link("mock01", "rust");
+    link["data"]["score"] = json!("lots");
+    let error = serde_json::from_value::<RedditResponse>(link).unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("t3"), "{}", message);
+    assert!(message.contains("data.score"), "{}", message);
+
+    let listing = fixtures::listing(vec![json!({"kind": "t1", "data": {"id": "abc"}})], None);
+    let error = serde_json::from_value::<RedditResponse>(listing).unwrap_err();
+    assert!(error.to_string().contains("data.children[0]"), "{}", error);
+
+    let error = serde_json::from_value::<RedditResponse>(json!({"data": {}})).unwrap_err();
+    assert!(error.to_string().contains("kind"), "{}", error);
+}
+
+#[test]
+fn unknown_kinds() -> anyhow::Result<()> {
+    let thing = json!({"kind": "t9", "data": {"id": "abc"}});
+    let error = serde_json::from_value::<RedditResponse>(thing.clone()).unwrap_err();
+    assert!(error.to_string().contains("t9"), "{}", error);
+
+    let Lenient(response) = serde_json::from_value::<Lenient<RedditResponse>>(thing)?;
+    assert!(response.kind == RedditDataType::Unknown);
+    match response.data {
+        RedditTypeResponse::Unknown { kind, data } => {
+            assert_eq!(kind, "t9");
+            assert_eq!(data["id"], "abc");
+        }
+        data => panic!("Expected an unknown kind. Got {:?}", data),
+    }
+
+    // Nested things are kept too. Strict decoding afterwards still fails
+    let listing = fixtures::listing(
+        vec![
+            fixtures::link("mock01", "rust"),
+            json!({"kind": "t9", "data": {"id": "abc"}}),
+        ],
+        None,
+    );
+    let Lenient(response) = serde_json::from_value::<Lenient<RedditResponse>>(listing.clone())?;
+    match response.data {
+        RedditTypeResponse::Listing(listing) => {
+            assert!(listing.children[0].kind == RedditDataType::Link);
+            assert!(listing.children[1].kind == RedditDataType::Unknown);
+        }
+        data => panic!("Expected a listing. Got {:?}", data),
+    }
+    assert!(serde_json::from_value::<RedditResponse>(listing).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn lenient_client() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let saved = fixtures::listing(
+        vec![
+            fixtures::link("mock01", "rust"),
+            json!({"kind": "t9", "data": {"id": "abc"}}),
+        ],
+        None,
+    );
+    reddit.respond("GET", "/user/rraw_test/saved", MockResponse::json(&saved));
+
+    let strict = reddit.password_client().await?;
+    let me = strict.me().await?;
+    assert!(me.saved(None).await.is_err());
+
+    let lenient = reddit
+        .password_client_with(|builder| builder.lenient(true))
+        .await?;
+    let me = lenient.me().await?;
+    let saved = me.saved(None).await?;
+    assert_eq!(saved.data.children.len(), 2);
+    assert!(saved.data.children[1].kind == RedditDataType::Unknown);
+    Ok(())
+}
+
+#[test]
+fn new_kinds() -> anyhow::Result<()> {
+    let things = vec![
+        fixtures::link("mock01", "rust"),
+        fixtures::more("m1", "t3_mock01", &["a", "b"]),
+        json!({"kind": "LabeledMulti", "data": {
+            "name": "rust_and_friends", "display_name": "Rust and Friends",
+            "path": "/user/rraw_test/m/rust_and_friends/", "subreddits": [{"name": "rust"}]
+        }}),
+        json!({"kind": "modaction", "data": {
+            "id": "ModAction_1", "action": "removelink", "mod": "rraw_test",
+            "created_utc": 1_600_000_000.0, "subreddit": "rust", "target_fullname": "t3_mock01"
+        }}),
+        json!({"kind": "UserList", "data": {"children": [
+            {"name": "rraw_test", "id": "t2_mockuser", "date": 1_600_000_000.0}
+        ]}}),
+        json!({"kind": "TrophyList", "data": {"trophies": [
+            {"kind": "t6", "data": {"name": "Verified Email", "award_id": "o"}}
+        ]}}),
+        json!({"kind": "t6", "data": {"name": "Five-Year Club"}}),
+        json!({"kind": "wikipage", "data": {
+            "content_md": "# Rules", "may_revise": false, "revision_by": fixtures::account("rraw_test")
+        }}),
+        json!({"kind": "stylesheet", "data": {
+            "stylesheet": "body {}", "images": [{"name": "snoo", "url": "https://example.com/snoo.png"}]
+        }}),
+        json!({"kind": "LiveUpdate", "data": {
+            "id": "abc", "body": "Live", "created_utc": 1_600_000_000.0, "embeds": []
+        }}),
+    ];
+    let listing: RedditResponse = serde_json::from_value(fixtures::listing(things, None))?;
+    let children = match listing.data {
+        RedditTypeResponse::Listing(listing) => listing.children,
+        data => panic!("Expected a listing. Got {:?}", data),
+    };
+    let kinds: Vec<String> = children
+        .iter()
+        .map(|child| child.kind.to_string())
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "t3",
+            "more",
+            "LabeledMulti",
+            "modaction",
+            "UserList",
+            "TrophyList",
+            "t6",
+            "wikipage",
+            "stylesheet",
+            "LiveUpdate"
+        ]
+    );
+    match &children[1].data {
+        RedditTypeResponse::More(more) => assert_eq!(more.children, ["a", "b"]),
+        data => panic!("Expected more. Got {:?}", data),
+    }
+    match &children[3].data {
+        RedditTypeResponse::ModAction(action) => assert_eq!(action.moderator, "rraw_test"),
+        data => panic!("Expected a modaction. Got {:?}", data),
+    }
+    match &children[5].data {
+        RedditTypeResponse::TrophyList(list) => {
+            assert_eq!(list.trophies[0].data.name, "Verified Email");
+            assert!(list.trophies[0].kind == RedditDataType::Award);
+        }
+        data => panic!("Expected a TrophyList. Got {:?}", data),
+    }
+    assert!(RedditDataType::from_str("t6")? == RedditDataType::Award);
+    assert_eq!(FullName::from_str("t6_abc")?.to_string(), "t6_abc");
+    Ok(())
+}
+
+#[test]
+fn reports_without_moderator_access() {
+    let mut comment = fixtures::comment("mockc1", "rust");
+    comment["data"]["num_reports"] = json!(null);
+    comment["data"]["mod_reports"] = json!(null);
+    let comment = serde_json::from_value::<RedditResponse>(comment).unwrap();
+    match comment.data {
+        RedditTypeResponse::Comment(comment) => {
+            assert!(comment.num_reports.is_none());
+            assert!(comment.mod_reports.is_empty());
+            assert!(comment.user_reports.is_empty());
+        }
+        data => panic!("Expected a comment got {:?}", data),
+    }
+}
+
+#[tokio::test]
+async fn mod_log() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let action = json!({"kind": "modaction", "data": {
+        "id": "ModAction_1", "action": "banuser", "mod": "rraw_test",
+        "created_utc": 1_600_000_000.0, "subreddit": "rust", "target_author": "spammer"
+    }});
+    reddit.respond(
+        "GET",
+        "/r/rust/about/log",
+        MockResponse::json(&fixtures::listing(vec![action], None)),
+    );
+    let client = reddit.password_client().await?;
+    let subreddit = client.subreddit("rust").await?;
+
+    let log = subreddit.mod_log(None).await?;
+    assert_eq!(log.data.children[0].data.action, "banuser");
+    assert_eq!(
+        log.data.children[0].data.target_author.as_deref(),
+        Some("spammer")
+    );
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use log::LevelFilter;
+use rraw::retry::RetryPolicy;
+use rraw::testing::{MockReddit, MockResponse};
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::from_millis(10),
+        ..RetryPolicy::default()
+    }
+}
+
+#[tokio::test]
+async fn retries_transient_get() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.retry_policy(fast_policy()))
+        .await?;
+    reddit.respond_once("GET", "/api/v1/me", MockResponse::new(503, ""));
+    reddit.respond_once(
+        "GET",
+        "/api/v1/me",
+        MockResponse::new(429, "").with_header("Retry-After", "0"),
+    );
+
+    client.me().await?;
+    assert_eq!(reddit.requests_to("/api/v1/me").len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.retry_policy(fast_policy()))
+        .await?;
+    reddit.respond("GET", "/api/v1/me", MockResponse::new(502, ""));
+
+    assert!(client.me().await.is_err());
+    assert_eq!(reddit.requests_to("/api/v1/me").len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_retry_post_by_default() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.retry_policy(fast_policy()))
+        .await?;
+    let me = client.me().await?;
+    reddit.respond_once("POST", "/api/compose", MockResponse::new(503, ""));
+
+    let result = me
+        .compose(
+            "rraw_friend".to_string(),
+            "Hello".to_string(),
+            "World".to_string(),
+        )
+        .await;
+    assert!(result.is_err());
+    assert_eq!(reddit.requests_to("/api/compose").len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn retries_post_when_enabled() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let policy = RetryPolicy {
+        retry_non_idempotent: true,
+        ..fast_policy()
+    };
+    let client = reddit
+        .password_client_with(|builder| builder.retry_policy(policy))
+        .await?;
+    let me = client.me().await?;
+    reddit.respond_once("POST", "/api/compose", MockResponse::new(503, ""));
+
+    me.compose(
+        "rraw_friend".to_string(),
+        "Hello".to_string(),
+        "World".to_string(),
+    )
+    .await?;
+    assert_eq!(reddit.requests_to("/api/compose").len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_retry_client_errors() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .password_client_with(|builder| builder.retry_policy(fast_policy()))
+        .await?;
+    reddit.respond_once("GET", "/api/v1/me", MockResponse::new(404, ""));
+
+    assert!(client.me().await.is_err());
+    assert_eq!(reddit.requests_to("/api/v1/me").len(), 1);
+    Ok(())
+}
+
+#[test]
+fn backoff_is_capped() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_secs(1),
+        max_backoff: Duration::from_secs(5),
+        jitter: false,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.backoff(1), Duration::from_secs(1));
+    assert_eq!(policy.backoff(2), Duration::from_secs(2));
+    assert_eq!(policy.backoff(3), Duration::from_secs(4));
+    assert_eq!(policy.backoff(10), Duration::from_secs(5));
+    let jittered = RetryPolicy {
+        jitter: true,
+        ..policy
+    };
+    let backoff = jittered.backoff(2);
+    assert!(backoff >= Duration::from_secs(1) && backoff <= Duration::from_secs(2));
+}
@@ -0,0 +1,157 @@
+use log::LevelFilter;
+use reqwest::Method;
+use serde_json::json;
+
+use rraw::auth::{CodeAuthenticator, Scope};
+use rraw::builder::Endpoints;
+use rraw::error::Error;
+use rraw::testing::{MockReddit, MockResponse};
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+#[test]
+fn parse_scopes() {
+    assert_eq!(
+        Scope::parse_granted("identity read privatemessages unknownscope"),
+        vec![Scope::Identity, Scope::Read, Scope::PrivateMessages]
+    );
+    assert!(Scope::Submit.granted_by(&[Scope::All]));
+    assert!(!Scope::Submit.granted_by(&[Scope::Read]));
+    assert_eq!(
+        Scope::required_for(&Method::POST, "/api/compose"),
+        Some(Scope::PrivateMessages)
+    );
+    assert_eq!(
+        Scope::required_for(&Method::GET, "/r/rust/about/log.json?limit=5"),
+        Some(Scope::ModLog)
+    );
+    assert_eq!(
+        Scope::required_for(&Method::GET, "/user/rraw_test/saved"),
+        Some(Scope::History)
+    );
+    assert_eq!(
+        Scope::required_for(&Method::GET, "/r/rust/new.json?limit=5"),
+        Some(Scope::Read)
+    );
+    assert_eq!(
+        Scope::required_for(&Method::GET, "/r/rust/about/modqueue"),
+        Some(Scope::ModPosts)
+    );
+    // Endpoints the crate does not know are not checked
+    assert_eq!(
+        Scope::required_for(&Method::GET, "/api/v1/unknown_endpoint"),
+        None
+    );
+    assert_eq!(
+        Scope::required_for(&Method::GET, "/r/rust/wiki/index"),
+        None
+    );
+
+    let url = CodeAuthenticator::generate_authorization_url(
+        &Endpoints::default(),
+        "client_id",
+        "http://localhost:8080",
+        "my_state",
+        "temporary",
+        vec![Scope::Identity, Scope::ModLog],
+    );
+    assert!(url.contains("scope=identity,modlog"));
+}
+
+#[tokio::test]
+async fn missing_scope() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    reddit.respond_once(
+        "POST",
+        "/api/v1/access_token",
+        MockResponse::json(&json!({
+            "access_token": "mock_access_token",
+            "expires_in": 3600,
+            "scope": "identity read",
+            "token_type": "bearer",
+        })),
+    );
+    let client = reddit.password_client().await?;
+    assert_eq!(
+        client.scopes().await,
+        Some(vec![Scope::Identity, Scope::Read])
+    );
+    assert!(client.has_scope(Scope::Read).await);
+    assert!(!client.has_scope(Scope::PrivateMessages).await);
+
+    let me = client.me().await?;
+    let subreddit = client.subreddit("rust").await?;
+    match me
+        .compose(
+            "rraw_friend".to_string(),
+            "Hello".to_string(),
+            "World".to_string(),
+        )
+        .await
+        .unwrap_err()
+    {
+        Error::MissingScope(scope) => assert_eq!(scope, Scope::PrivateMessages),
+        error => panic!("Unexpected Error {}", error),
+    }
+    assert!(matches!(
+        subreddit.mod_log(None).await.unwrap_err(),
+        Error::MissingScope(Scope::ModLog)
+    ));
+    assert!(reddit.requests_to("/api/compose").is_empty());
+    assert!(reddit.requests_to("/r/rust/about/log").is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn all_scopes() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    assert!(client.has_scope(Scope::ModLog).await);
+    client.me().await?.saved(None).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn scopes_follow_refresh() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let token = |scope: &str| {
+        MockResponse::json(&json!({
+            "access_token": "mock_access_token",
+            "expires_in": 3600,
+            "scope": scope,
+            "token_type": "bearer",
+        }))
+    };
+    reddit.respond_once("POST", "/api/v1/access_token", token("identity"));
+    reddit.respond_once("POST", "/api/v1/access_token", token("identity read"));
+    let client = reddit.password_client().await?;
+    assert!(!client.has_scope(Scope::Read).await);
+    assert!(matches!(
+        client
+            .subreddit("rust")
+            .await
+            .err()
+            .expect("Read was granted"),
+        Error::MissingScope(Scope::Read)
+    ));
+
+    client.re_login().await?;
+    assert_eq!(
+        client.scopes().await,
+        Some(vec![Scope::Identity, Scope::Read])
+    );
+    client.subreddit("rust").await?;
+    assert_eq!(reddit.requests_to("/r/rust/about").len(), 1);
+    Ok(())
+}
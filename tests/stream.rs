@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use futures_util::{StreamExt, TryStreamExt};
+use log::LevelFilter;
+use rraw::auth::AnonymousAuthenticator;
+use rraw::stream::StreamOptions;
+use rraw::testing::{fixtures, MockReddit, MockResponse};
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+fn fast_options() -> StreamOptions {
+    StreamOptions {
+        min_interval: Duration::from_millis(10),
+        max_interval: Duration::from_millis(40),
+        ..StreamOptions::default()
+    }
+}
+
+fn new_links(ids: &[&str]) -> MockResponse {
+    let children = ids.iter().map(|id| fixtures::link(id, "rust")).collect();
+    MockResponse::json(&fixtures::listing(children, None))
+}
+
+#[tokio::test]
+async fn yields_unseen_submissions_oldest_first() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    let subreddit = client.subreddit("rust").await?;
+    reddit.respond_once("GET", "/r/rust/new", new_links(&["b", "a"]));
+    reddit.respond_once("GET", "/r/rust/new", new_links(&["b", "a"]));
+    reddit.respond("GET", "/r/rust/new", new_links(&["d", "c", "b", "a"]));
+
+    let submissions: Vec<_> = subreddit
+        .stream_submissions(Some(fast_options()))
+        .take(4)
+        .try_collect()
+        .await?;
+
+    let ids: Vec<&str> = submissions.iter().map(|s| s.data.id.as_str()).collect();
+    assert_eq!(ids, ["a", "b", "c", "d"]);
+    assert_eq!(reddit.requests_to("/r/rust/new").len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn skips_existing() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    reddit.respond_once("GET", "/r/rust+all/new", new_links(&["b", "a"]));
+    reddit.respond("GET", "/r/rust+all/new", new_links(&["c", "b", "a"]));
+    let options = StreamOptions {
+        skip_existing: true,
+        ..fast_options()
+    };
+
+    let mut stream = Box::pin(client.stream_submissions("rust+all", Some(options)));
+    let submission = stream.try_next().await?.unwrap();
+    assert_eq!(submission.data.id, "c");
+    Ok(())
+}
+
+#[tokio::test]
+async fn continues_after_errors() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
+    let subreddit = client.subreddit("rust").await?;
+    reddit.respond_once("GET", "/r/rust/comments", MockResponse::new(404, ""));
+
+    let mut stream = Box::pin(subreddit.stream_comments(Some(fast_options())));
+    assert!(stream.next().await.unwrap().is_err());
+    let comment = stream.next().await.unwrap()?;
+    assert_eq!(comment.data.id, "mockc1");
+    let comment = stream.next().await.unwrap()?;
+    assert_eq!(comment.data.id, "mockc2");
+    Ok(())
+}
@@ -1,6 +1,15 @@
 use log::LevelFilter;
-use rraw::auth::AnonymousAuthenticator;
-use rraw::Client;
+use std::str::FromStr;
+use std::time::Duration;
+
+use rraw::auth::{AnonymousAuthenticator, PasswordAuthenticator};
+use rraw::error::reddit_error::RedditError;
+use rraw::error::Error;
+use rraw::responses::{FullName, RedditTypeResponse};
+use rraw::submission::SubmissionRetriever;
+use rraw::subreddit::submit::Submit;
+use rraw::testing::{MockReddit, MockResponse};
+use rraw::utils::options::{FeedOption, ModQueueFilter};
 
 fn init() {
     if let Err(error) = env_logger::builder()
@@ -12,18 +21,260 @@ fn init() {
     }
 }
 
-#[ignore]
 #[tokio::test]
 async fn generic() -> anyhow::Result<()> {
     init();
-    let client = Client::login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
+    let reddit = MockReddit::start()?;
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(AnonymousAuthenticator::new())
+        .await?;
 
     let subreddit = client.subreddit("askreddit").await;
 
     assert!(subreddit.is_ok());
     let data = subreddit.unwrap();
+    assert_eq!(data.subreddit.display_name, "askreddit");
     for (id, value) in data.subreddit.other.iter() {
         println!("{id}: {value:?}");
     }
-    return Ok(());
+    let hot = data.hot(None).await?;
+    assert_eq!(hot.data.children.len(), 2);
+    assert_eq!(reddit.requests()[1].path, "/r/askreddit/hot");
+    Ok(())
+}
+
+#[tokio::test]
+async fn submit() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let subreddit = client.subreddit("rust").await?;
+
+    let post = subreddit
+        .submit(
+            Submit::self_post("Hello & Welcome", "a=b #rust ü")
+                .flair_id("flair")
+                .spoiler(true)
+                .send_replies(false),
+        )
+        .await?;
+    assert_eq!(post.name.to_string(), "t3_mocknew");
+    assert_eq!(post.id, "mocknew");
+    let form = reddit.requests_to("/api/submit")[0].form();
+    assert_eq!(form["sr"], "rust");
+    assert_eq!(form["kind"], "self");
+    assert_eq!(form["title"], "Hello & Welcome");
+    assert_eq!(form["text"], "a=b #rust ü");
+    assert_eq!(form["flair_id"], "flair");
+    assert_eq!(form["spoiler"], "true");
+    assert_eq!(form["sendreplies"], "false");
+    assert_eq!(form["api_type"], "json");
+
+    subreddit
+        .submit(Submit::crosspost("Look", FullName::from_str("t3_abc")?))
+        .await?;
+    let form = reddit.requests_to("/api/submit")[1].form();
+    assert_eq!(form["kind"], "crosspost");
+    assert_eq!(form["crosspost_fullname"], "t3_abc");
+    Ok(())
+}
+
+#[tokio::test]
+async fn submit_errors() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let subreddit = client.subreddit("rust").await?;
+    reddit.respond_once(
+        "POST",
+        "/api/submit",
+        MockResponse::new(
+            200,
+            r#"{"json": {"errors": [["ALREADY_SUB", "that link has already been submitted", "url"]]}}"#,
+        ),
+    );
+
+    let error = subreddit
+        .submit(Submit::link("Link", "https://www.rust-lang.org"))
+        .await
+        .unwrap_err();
+    match error {
+        Error::RedditError(RedditError::Unknown(code, message, field)) => {
+            assert_eq!(code, "ALREADY_SUB");
+            assert_eq!(message, "that link has already been submitted");
+            assert_eq!(field.as_deref(), Some("url"));
+        }
+        error => panic!("Unexpected Error {}", error),
+    }
+
+    reddit.respond_once(
+        "POST",
+        "/api/submit",
+        MockResponse::new(
+            200,
+            r#"{"json": {"errors": [["SUBREDDIT_NOEXIST", "that subreddit doesn't exist", "sr"]]}}"#,
+        ),
+    );
+    let error = subreddit
+        .submit(Submit::self_post("Title", "Text"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::RedditError(RedditError::SubredditNotExist)
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn compose_errors() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let subreddit = client.subreddit("rust").await?;
+    reddit.respond_once(
+        "POST",
+        "/api/compose",
+        MockResponse::new(
+            200,
+            r#"{"json": {"ratelimit": 90.0, "errors": [["RATELIMIT", "you are doing that too much. try again in 2 minutes.", "ratelimit"]]}}"#,
+        ),
+    );
+    reddit.respond_once(
+        "POST",
+        "/api/compose",
+        MockResponse::new(
+            200,
+            r#"{"json": {"errors": [["RATELIMIT", "you are doing that too much. try again in 2 minutes.", "ratelimit"]]}}"#,
+        ),
+    );
+    reddit.respond_once(
+        "POST",
+        "/api/compose",
+        MockResponse::new(
+            200,
+            r#"{"json": {"errors": [["NO_TEXT", "we need something here", "subject"]]}}"#,
+        ),
+    );
+    let compose = || {
+        subreddit.compose(
+            "rraw_friend".to_string(),
+            "Hello".to_string(),
+            "World".to_string(),
+        )
+    };
+
+    match compose().await.unwrap_err() {
+        Error::RedditError(RedditError::RateLimited { retry_after, .. }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(90)));
+        }
+        error => panic!("Unexpected Error {}", error),
+    }
+    match compose().await.unwrap_err() {
+        Error::RedditError(RedditError::RateLimited { retry_after, .. }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(120)));
+        }
+        error => panic!("Unexpected Error {}", error),
+    }
+    match compose().await.unwrap_err() {
+        Error::RedditError(RedditError::NoText { field }) => {
+            assert_eq!(field.as_deref(), Some("subject"));
+        }
+        error => panic!("Unexpected Error {}", error),
+    }
+    let response = compose().await?;
+    assert!(response["json"]["errors"].as_array().unwrap().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn encoded_requests() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let auth = PasswordAuthenticator::new("client_id", "client_secret", "rraw_test", "p&ss=word");
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth)
+        .await?;
+    let login = &reddit.requests_to("/api/v1/access_token")[0];
+    assert_eq!(login.form()["password"], "p&ss=word");
+    assert_eq!(login.form()["username"], "rraw_test");
+
+    let subreddit = client.subreddit("rust").await?;
+    subreddit
+        .compose(
+            "rraw_friend".to_string(),
+            "Fish & Chips = #1".to_string(),
+            "ünïcode + 100%".to_string(),
+        )
+        .await?;
+    let compose = reddit.requests_to("/api/compose")[0].form();
+    assert_eq!(compose["subject"], "Fish & Chips = #1");
+    assert_eq!(compose["text"], "ünïcode + 100%");
+    assert_eq!(compose["to"], "rraw_friend");
+    assert_eq!(compose.len(), 5);
+
+    client.search_subreddits("rust & go", Some(5), None).await?;
+    let search = &reddit.requests_to("/subreddits/search")[0];
+    let query = search.query_pairs();
+    assert_eq!(query["q"], "rust & go");
+    assert_eq!(query["limit"], "5");
+    assert!(!search.query.as_deref().unwrap_or_default().contains(' '));
+    Ok(())
+}
+
+#[tokio::test]
+async fn mod_queues() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let client = reddit.password_client().await?;
+    let subreddit = client.subreddit("rust").await?;
+
+    let queue = subreddit.modqueue(None, None).await?;
+    assert_eq!(queue.data.children.len(), 2);
+    match &queue.data.children[0].data {
+        RedditTypeResponse::Link(submission) => {
+            assert_eq!(submission.num_reports, Some(2));
+            assert_eq!(submission.mod_reports[0].reason, "Breaks rule 1");
+            assert_eq!(submission.mod_reports[0].moderator, "rraw_test");
+            assert_eq!(submission.user_reports[0].reason.as_deref(), Some("Spam"));
+            assert_eq!(submission.user_reports[0].count, 1);
+            assert!(!submission.user_reports[0].snoozed);
+            assert!(submission.user_reports[0].can_snooze);
+        }
+        data => panic!("Expected a submission got {:?}", data),
+    }
+    match &queue.data.children[1].data {
+        RedditTypeResponse::Comment(comment) => {
+            assert_eq!(comment.mod_reports[0].moderator, "rraw_test");
+            assert_eq!(comment.user_reports[0].count, 1);
+        }
+        data => panic!("Expected a comment got {:?}", data),
+    }
+    assert!(reddit.requests_to("/r/rust/about/modqueue")[0]
+        .query
+        .is_none());
+
+    let feed = FeedOption {
+        limit: Some(10),
+        ..FeedOption::default()
+    };
+    subreddit
+        .reports(Some(ModQueueFilter::Comments), Some(feed))
+        .await?;
+    let query = reddit.requests_to("/r/rust/about/reports")[0].query_pairs();
+    assert_eq!(query["only"], "comments");
+    assert_eq!(query["limit"], "10");
+
+    subreddit.spam(Some(ModQueueFilter::Links), None).await?;
+    subreddit.edited(None, None).await?;
+    subreddit.unmoderated(None, None).await?;
+    assert_eq!(
+        reddit.requests_to("/r/rust/about/spam")[0].query_pairs()["only"],
+        "links"
+    );
+    assert_eq!(reddit.requests_to("/r/rust/about/edited").len(), 1);
+    assert_eq!(reddit.requests_to("/r/rust/about/unmoderated").len(), 1);
+    Ok(())
 }
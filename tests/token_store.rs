@@ -0,0 +1,178 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::LevelFilter;
+
+use rraw::auth::{
+    Authenticator, CodeAuthenticator, FileTokenStore, MemoryTokenStore, StoredToken,
+    TokenAuthenticator, TokenStore,
+};
+use rraw::testing::MockReddit;
+
+fn init() {
+    if let Err(error) = env_logger::builder()
+        .is_test(true)
+        .filter_level(LevelFilter::Debug)
+        .try_init()
+    {
+        println!("Logger Failed to Init Error: {}", error);
+    }
+}
+
+#[tokio::test]
+async fn memory_store_shares_tokens() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let store = MemoryTokenStore::new();
+    let auth = TokenAuthenticator::new("client_id", "client_secret", "refresh_token")
+        .with_store(store.clone());
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth)
+        .await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+    client.me().await?;
+
+    let stored = store.load().await?.expect("Token was not saved");
+    assert_eq!(stored.token.as_deref(), Some("mock_access_token"));
+    assert_eq!(stored.refresh_token.as_deref(), Some("refresh_token"));
+    assert!(!stored.is_expired());
+
+    // A second worker picks up the saved access token instead of logging in again
+    let auth = TokenAuthenticator::new("client_id", "client_secret", "other_refresh_token")
+        .with_store(store.clone());
+    let worker = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth)
+        .await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+    assert_eq!(worker.refresh_token().as_deref(), Some("refresh_token"));
+    worker.me().await?;
+
+    let mut auth = TokenAuthenticator::new("client_id", "client_secret", "refresh_token")
+        .with_store(store.clone());
+    let http = reqwest::Client::new();
+    auth.login(&http, "RRAW Test (by u/KingTuxWH)", &reddit.endpoints())
+        .await?;
+    assert_eq!(auth.get_refresh_token().as_deref(), Some("refresh_token"));
+    auth.logout(&http, "RRAW Test (by u/KingTuxWH)", &reddit.endpoints())
+        .await?;
+    assert!(store.load().await?.is_none());
+    assert_eq!(auth.get_refresh_token(), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn file_store_survives_restart() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let path = std::env::temp_dir().join(format!("rraw_token_{}.json", std::process::id()));
+    let store = FileTokenStore::new(&path);
+    store.clear().await?;
+    assert!(store.load().await?.is_none());
+
+    let auth = CodeAuthenticator::new("client_id", "client_secret", "code", "http://localhost")
+        .with_store(FileTokenStore::new(&path));
+    reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth)
+        .await?;
+    let stored = store.load().await?.expect("Token was not saved");
+    assert_eq!(stored.refresh_token.as_deref(), Some("mock_refresh_token"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    // The temporary file was renamed
+    let name = path.file_name().unwrap().to_string_lossy().to_string();
+    let leftovers = std::fs::read_dir(path.parent().unwrap())?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|file| file.starts_with(&name) && file != &name)
+        .count();
+    assert_eq!(leftovers, 0);
+
+    // After a restart the expired access token is refreshed instead of redeeming the code again
+    store
+        .save(&StoredToken {
+            expiration_time: Some(0),
+            ..stored
+        })
+        .await?;
+    let mut auth = CodeAuthenticator::new(
+        "client_id",
+        "client_secret",
+        "used_code",
+        "http://localhost",
+    )
+    .with_store(FileTokenStore::new(&path));
+    let http = reqwest::Client::new();
+    auth.login(&http, "RRAW Test (by u/KingTuxWH)", &reddit.endpoints())
+        .await?;
+    let logins = reddit.requests_to("/api/v1/access_token");
+    assert_eq!(logins.len(), 2);
+    assert_eq!(logins[1].form()["grant_type"], "refresh_token");
+    assert_eq!(logins[1].form()["refresh_token"], "mock_refresh_token");
+    assert!(!store
+        .load()
+        .await?
+        .expect("Token was not saved")
+        .is_expired());
+
+    assert_eq!(
+        auth.get_refresh_token().as_deref(),
+        Some("mock_refresh_token")
+    );
+    auth.logout(&http, "RRAW Test (by u/KingTuxWH)", &reddit.endpoints())
+        .await?;
+    assert!(!path.exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn stored_token_within_margin() -> anyhow::Result<()> {
+    init();
+    let reddit = MockReddit::start()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    let store = MemoryTokenStore::new();
+    store
+        .save(&StoredToken {
+            token: Some("stored_access_token".to_string()),
+            expiration_time: Some(now + 30_000),
+            refresh_token: Some("refresh_token".to_string()),
+            scope: Some("*".to_string()),
+        })
+        .await?;
+    let stored = store.load().await?.expect("Token was not saved");
+    assert!(!stored.is_expired());
+    assert!(stored.expires_within(Duration::from_secs(60)));
+    assert!(!stored.expires_within(Duration::from_secs(10)));
+
+    // 30 seconds left is within the default margin of 60 seconds
+    let auth = TokenAuthenticator::new("client_id", "client_secret", "refresh_token")
+        .with_store(store.clone());
+    reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .login(auth)
+        .await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+
+    store.save(&stored).await?;
+    let auth = TokenAuthenticator::new("client_id", "client_secret", "refresh_token")
+        .with_store(store.clone());
+    let client = reddit
+        .client_builder("RRAW Test (by u/KingTuxWH)")
+        .refresh_margin(Duration::from_secs(10))
+        .login(auth)
+        .await?;
+    client.me().await?;
+    assert_eq!(reddit.requests_to("/api/v1/access_token").len(), 1);
+    let me = reddit.requests_to("/api/v1/me");
+    assert_eq!(
+        me.last()
+            .and_then(|request| request.header("authorization")),
+        Some("Bearer stored_access_token")
+    );
+    Ok(())
+}
@@ -2,7 +2,7 @@
 mod user_tests {
     use log::LevelFilter;
     use rraw::auth::AnonymousAuthenticator;
-    use rraw::Client;
+    use rraw::testing::MockReddit;
     pub static TEST_USERS: [&str; 3] = ["KingTuxWH", "TheSmartKing", "Princeflower13"];
 
     fn init() {
@@ -17,17 +17,26 @@ mod user_tests {
     #[tokio::test]
     pub async fn test_search() -> anyhow::Result<()> {
         init();
-        let client =
-            Client::login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
+        let reddit = MockReddit::start()?;
+        let client = reddit
+            .client_builder("RRAW Test (by u/KingTuxWH)")
+            .login(AnonymousAuthenticator::new())
+            .await?;
         let users = client.search_users("King", None, None).await?;
         assert!(users.data.children.len() > 1);
+        let request = &reddit.requests()[0];
+        assert_eq!(request.path, "/users/search");
+        assert_eq!(request.query_pairs()["q"], "King");
         Ok(())
     }
     #[tokio::test]
     pub async fn test_get_user() -> anyhow::Result<()> {
         init();
-        let client =
-            Client::login(AnonymousAuthenticator::new(), "RRAW Test (by u/KingTuxWH)").await?;
+        let reddit = MockReddit::start()?;
+        let client = reddit
+            .client_builder("RRAW Test (by u/KingTuxWH)")
+            .login(AnonymousAuthenticator::new())
+            .await?;
         for username in TEST_USERS {
             let user = client.user(username).await;
             assert!(